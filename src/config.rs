@@ -6,10 +6,22 @@ use anyhow::{Result, anyhow};
 use serde::Deserialize;
 use xdg::BaseDirectories;
 
+use crate::host::{Libc, Target};
+use crate::manifest::Repo;
+
+/// Number of previous versions kept for rollbacks if not configured otherwise.
+const DEFAULT_RETAINED_VERSIONS: usize = 1;
+
 #[derive(Deserialize)]
 struct Toml {
     /// Installation path
-    install_path: PathBuf,
+    install_path: Option<PathBuf>,
+    /// Number of previously installed versions to keep per binary
+    retained_versions: Option<usize>,
+    /// Minimum age in days of releases to install
+    cooldown: Option<u32>,
+    /// C library to choose builds for instead of the detected one
+    libc: Option<Libc>,
 }
 
 pub(crate) struct Config {
@@ -40,8 +52,12 @@ impl Config {
     /// Return installation target directory. If not explicitly specified in the configuration,
     /// check if `~/.local/bin` is in `$PATH` and return that.
     pub(crate) fn install_path(&self) -> Result<PathBuf> {
-        if let Some(toml) = &self.toml {
-            return Ok(toml.install_path.clone());
+        if let Some(path) = self
+            .toml
+            .as_ref()
+            .and_then(|toml| toml.install_path.as_ref())
+        {
+            return Ok(path.clone());
         }
 
         // TODO: test
@@ -57,4 +73,47 @@ impl Config {
             "no suitable destination directory found, consider configuring one"
         ))
     }
+
+    /// Return the XDG data directory shell completions and man pages are installed into.
+    pub(crate) fn data_home(&self) -> Option<PathBuf> {
+        self.base_dir.data_home.clone()
+    }
+
+    /// Return the number of previously installed versions that are kept per binary.
+    pub(crate) fn retained_versions(&self) -> usize {
+        self.toml
+            .as_ref()
+            .and_then(|toml| toml.retained_versions)
+            .unwrap_or(DEFAULT_RETAINED_VERSIONS)
+    }
+
+    /// Return the minimum age in days releases must have before being installed.
+    pub(crate) fn cooldown(&self) -> u32 {
+        self.toml
+            .as_ref()
+            .and_then(|toml| toml.cooldown)
+            .unwrap_or_default()
+    }
+
+    /// Return the configured libc or the one detected on the host.
+    fn libc(&self) -> Option<Libc> {
+        self.toml
+            .as_ref()
+            .and_then(|toml| toml.libc)
+            .or_else(crate::host::detect_libc)
+    }
+
+    /// Return the target of this host with the configured or detected libc.
+    pub(crate) fn host(&self) -> Target {
+        Target::host(self.libc())
+    }
+
+    /// Return directory to keep the given `version` of `repo` in, creating it if necessary.
+    pub(crate) fn retained_path(&self, repo: &Repo, version: &str) -> Result<PathBuf> {
+        let version = version.replace(['/', '\\'], "_");
+
+        Ok(self
+            .base_dir
+            .create_data_directory(format!("versions/{}/{}/{version}", repo.owner, repo.name))?)
+    }
 }
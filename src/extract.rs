@@ -1,47 +1,432 @@
 //! Extractors for various archive types.
-use std::io::Cursor;
+use std::ffi::OsStr;
 use std::os::unix::fs::PermissionsExt;
 use std::path::{Path, PathBuf};
 
 use anyhow::{Result, anyhow};
+use async_compression::tokio::bufread::{
+    BzDecoder, GzipDecoder, Lz4Decoder, XzDecoder, ZstdDecoder,
+};
 use async_zip::base::read::seek::ZipFileReader;
 use futures_lite::StreamExt;
-use tokio::io::AsyncRead;
+use regex::Regex;
+use tokio::io::{
+    AsyncBufRead, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncSeek, AsyncSeekExt, BufReader,
+};
 use tokio_util::compat::FuturesAsyncReadCompatExt;
 
-/// Async variant of [`write`].
+/// Write `input` to `dest` with permissions `mode`. The content goes to a temporary file next to
+/// `dest` first, which is synced and then renamed over `dest`. Thus, an interrupted download never
+/// leaves a truncated file behind and running executables can be replaced. The temporary file is
+/// removed if writing fails or the future is dropped, e.g. on Ctrl-C.
 pub(crate) async fn write_async<R: AsyncRead + Unpin>(
-    mut input: R,
+    input: R,
     dest: &Path,
     mode: u32,
 ) -> Result<()> {
-    let mut output = tokio::fs::File::create(dest).await?;
+    stage(input, dest, mode).await?.persist(dest)?;
+
+    Ok(())
+}
+
+/// Write `input` with permissions `mode` to a synced temporary file next to `dest`, which can be
+/// renamed to `dest` atomically. The file is removed when the returned path is dropped.
+async fn stage<R: AsyncRead + Unpin>(
+    mut input: R,
+    dest: &Path,
+    mode: u32,
+) -> Result<tempfile::TempPath> {
+    let dir = dest
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or(Path::new("."));
+
+    let (file, temp_path) = tempfile::Builder::new()
+        .prefix(".binge-")
+        .tempfile_in(dir)?
+        .into_parts();
+
+    let mut output = tokio::fs::File::from_std(file);
     tokio::io::copy(&mut input, &mut output).await?;
 
-    let mut permissions = output.metadata().await?.permissions();
-    permissions.set_mode(mode & 0o755);
-    output.set_permissions(permissions).await?;
+    output
+        .set_permissions(std::fs::Permissions::from_mode(mode & 0o755))
+        .await?;
+    output.sync_all().await?;
 
-    Ok(())
+    Ok(temp_path)
+}
+
+/// Copy `input` into an anonymous temporary file in `dir` and return it rewound, so that archives
+/// needing random access can be read without keeping them in memory. The file vanishes once it is
+/// dropped.
+pub(crate) async fn spool<R: AsyncRead + Unpin>(
+    mut input: R,
+    dir: &Path,
+) -> Result<BufReader<tokio::fs::File>> {
+    let mut file = tokio::fs::File::from_std(tempfile::tempfile_in(dir)?);
+    tokio::io::copy(&mut input, &mut file).await?;
+    file.rewind().await?;
+
+    Ok(BufReader::new(file))
 }
 
-/// Extract contained binary and return [`PathBuf`] to where it is located now.
-pub(crate) async fn extract_zip<B: AsRef<[u8]> + Unpin>(
-    bytes: B,
-    dest_dir: &Path,
-) -> Result<PathBuf> {
-    let mut archive = ZipFileReader::with_tokio(Cursor::new(bytes)).await?;
+/// Magic bytes ELF executables start with.
+const ELF_MAGIC: [u8; 4] = [0x7f, b'E', b'L', b'F'];
+
+/// Executables to extract from an archive.
+pub(crate) struct Select {
+    /// Glob patterns of wanted executables and whether they match the whole path instead of the
+    /// file name. If empty, all executables are wanted.
+    bins: Vec<(Regex, bool)>,
+    /// Names an executable is preferred as main executable for, the first one being the name it is
+    /// installed as.
+    names: Vec<String>,
+}
+
+impl Select {
+    /// Select executables matching one of the `bins` globs or all of them if there are none,
+    /// preferring one called like any of `names`, e.g. the rename and the repository name, as main
+    /// executable.
+    pub(crate) fn new(bins: &[String], names: &[&str]) -> Self {
+        let bins = bins
+            .iter()
+            .map(|bin| (glob(bin), bin.contains('/')))
+            .collect();
+
+        Self {
+            bins,
+            names: names.iter().map(ToString::to_string).collect(),
+        }
+    }
+
+    /// Name the main executable is installed as.
+    pub(crate) fn name(&self) -> &str {
+        self.names.first().map_or("", String::as_str)
+    }
+
+    /// Returns `true` if executables are chosen automatically because no globs are given.
+    fn is_automatic(&self) -> bool {
+        self.bins.is_empty()
+    }
+
+    /// Return the rank of the executable at `path` among all selected ones, lower being better, or
+    /// `None` if it is not selected. Executables are ranked by the first glob they match or,
+    /// without globs, the ones named like one of the main executable names come first.
+    fn rank(&self, path: &Path) -> Option<usize> {
+        let path = path.strip_prefix("./").unwrap_or(path);
+        let name = path.file_name()?.to_str()?;
+
+        if self.bins.is_empty() {
+            let name = name.to_ascii_lowercase();
+            let mains = self
+                .names
+                .iter()
+                .map(|main| main.to_ascii_lowercase())
+                .collect::<Vec<_>>();
+
+            return Some(if mains.contains(&name) {
+                0
+            } else if mains.iter().any(|main| name.starts_with(main)) {
+                1
+            } else {
+                2
+            });
+        }
+
+        self.bins.iter().position(|(glob, whole)| {
+            if *whole {
+                path.to_str().is_some_and(|path| glob.is_match(path))
+            } else {
+                glob.is_match(name)
+            }
+        })
+    }
+}
+
+/// Translate the glob `pattern` supporting `*`, `**` and `?` into an anchored regex.
+fn glob(pattern: &str) -> Regex {
+    let expr = regex::escape(pattern)
+        .replace(r"\*\*", ".*")
+        .replace(r"\*", "[^/]*")
+        .replace(r"\?", "[^/]");
+
+    Regex::new(&format!("^{expr}$")).expect("compiling the regex")
+}
+
+/// Return `true` if `path` looks like a shared library, i.e. it is named `*.so*` or `*.dylib` or
+/// located in a `lib` directory.
+fn is_library(path: &Path) -> bool {
+    let library = Regex::new(r"\.(?:so(?:\.[\d.]+)?|dylib)$").expect("compiling the regex");
+
+    path.to_str().is_some_and(|path| library.is_match(path))
+        || path.parent().is_some_and(|parent| {
+            parent.iter().any(|component| {
+                ["lib", "lib32", "lib64"]
+                    .map(OsStr::new)
+                    .contains(&component)
+            })
+        })
+}
+
+/// Directories extracted files are installed into.
+pub(crate) struct Dirs {
+    /// Directory for executables.
+    pub bin: PathBuf,
+    /// XDG data directory for shell completions and man pages, which are skipped if `None`.
+    pub data: Option<PathBuf>,
+    /// Paths installed by other binaries, which are never overwritten.
+    pub taken: Vec<PathBuf>,
+}
+
+impl Dirs {
+    /// Fail if `dest` is installed by another binary.
+    pub(crate) fn ensure_free(&self, dest: &Path) -> Result<()> {
+        if self.taken.iter().any(|path| path == dest) {
+            return Err(anyhow!(
+                "refusing to overwrite {dest:?} installed by another binary"
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+/// Files extracted from an archive.
+#[derive(Debug, Default)]
+pub(crate) struct Extracted {
+    /// Installed executables, the main one first.
+    pub executables: Vec<PathBuf>,
+    /// Installed shell completions and man pages.
+    pub extras: Vec<PathBuf>,
+}
+
+impl Extracted {
+    /// Return a single executable at `path`.
+    pub(crate) fn single(path: PathBuf) -> Self {
+        Self {
+            executables: vec![path],
+            extras: Vec::new(),
+        }
+    }
+}
+
+/// Return where the shell completion or man page at `path` of an archive is installed relative to
+/// the XDG data directory or `None` if it is neither. Completions must be located in a directory
+/// named like `complete`, `completions` or `autocomplete`.
+fn extra_path(path: &Path) -> Option<PathBuf> {
+    let man = Regex::new(r"^[\w+-]*[A-Za-z_+-]\.([1-8])(?:\.gz)?$").expect("compiling the regex");
+
+    let name = path.file_name()?.to_str()?;
+
+    if let Some(captures) = man.captures(name) {
+        return Some(
+            Path::new("man")
+                .join(format!("man{}", &captures[1]))
+                .join(name),
+        );
+    }
+
+    let dirs = path
+        .parent()?
+        .iter()
+        .filter_map(|component| component.to_str())
+        .map(str::to_ascii_lowercase)
+        .collect::<Vec<_>>();
+
+    if !dirs.iter().any(|dir| dir.contains("complet")) {
+        return None;
+    }
+
+    let within = |shell: &str| dirs.iter().any(|dir| dir.contains(shell));
 
-    let (index, mode, dest) = archive
+    if let Some(stem) = name.strip_suffix(".fish") {
+        Some(Path::new("fish/vendor_completions.d").join(format!("{stem}.fish")))
+    } else if let Some(stem) = name.strip_suffix(".zsh") {
+        let stem = stem.trim_start_matches('_');
+        Some(Path::new("zsh/site-functions").join(format!("_{stem}")))
+    } else if let Some(stem) = name.strip_suffix(".bash") {
+        Some(Path::new("bash-completion/completions").join(stem))
+    } else if name.contains('.') {
+        None
+    } else if name.starts_with('_') || within("zsh") {
+        let stem = name.trim_start_matches('_');
+        Some(Path::new("zsh/site-functions").join(format!("_{stem}")))
+    } else if within("fish") {
+        Some(Path::new("fish/vendor_completions.d").join(format!("{name}.fish")))
+    } else if within("bash") {
+        Some(Path::new("bash-completion/completions").join(name))
+    } else {
+        None
+    }
+}
+
+/// Where an archive entry is installed.
+enum Place {
+    /// Executable with its rank according to [`Select::rank`]. If `sniff` is set, it is only
+    /// installed if it turns out to be an ELF binary or a script.
+    Executable {
+        rank: usize,
+        dest: PathBuf,
+        sniff: bool,
+    },
+    /// Shell completion or man page.
+    Extra(PathBuf),
+}
+
+/// Files extracted so far while walking through an archive. They are staged next to their
+/// destinations and only moved into place once the whole archive is read, so that a failure
+/// midway leaves the installed files untouched.
+#[derive(Default)]
+struct Collected {
+    executables: Vec<(usize, PathBuf)>,
+    extras: Vec<PathBuf>,
+    staged: Vec<(tempfile::TempPath, PathBuf)>,
+}
+
+impl Collected {
+    /// Decide where the entry at `path` is installed, if at all. Shell completions and man pages
+    /// are never treated as executables, even if their executable bit is set. Executables must
+    /// additionally be accepted by `accept` and, if chosen automatically, must not be shared
+    /// libraries. Fails if the entry would overwrite a file of another binary.
+    fn place(
+        &self,
+        path: &Path,
+        executable: bool,
+        dirs: &Dirs,
+        select: &Select,
+        accept: impl Fn(&Path) -> bool,
+    ) -> Result<Option<Place>> {
+        let path = path.strip_prefix("./").unwrap_or(path);
+
+        if let Some(extra) = extra_path(path) {
+            let Some(dest) = dirs.data.as_ref().map(|data| data.join(extra)) else {
+                return Ok(None);
+            };
+
+            if self.extras.contains(&dest) {
+                return Ok(None);
+            }
+
+            dirs.ensure_free(&dest)?;
+
+            return Ok(Some(Place::Extra(dest)));
+        }
+
+        if executable && accept(path) {
+            let automatic = select.is_automatic();
+
+            if automatic && is_library(path) {
+                return Ok(None);
+            }
+
+            let (Some(rank), Some(name)) = (select.rank(path), path.file_name()) else {
+                return Ok(None);
+            };
+
+            let dest = dirs.bin.join(name);
+
+            if self.executables.iter().any(|(_, path)| *path == dest) {
+                return Ok(None);
+            }
+
+            dirs.ensure_free(&dest)?;
+
+            return Ok(Some(Place::Executable {
+                rank,
+                dest,
+                sniff: automatic,
+            }));
+        }
+
+        Ok(None)
+    }
+
+    /// Write `input` to `place`, using `mode` for executables. Sniffed executables that are
+    /// neither ELF binaries nor scripts are consumed but not written.
+    async fn write<R: AsyncRead + Unpin>(
+        &mut self,
+        mut input: R,
+        place: Place,
+        mode: u32,
+    ) -> Result<()> {
+        match place {
+            Place::Executable { rank, dest, sniff } => {
+                let mut head = Vec::with_capacity(ELF_MAGIC.len());
+                (&mut input)
+                    .take(ELF_MAGIC.len() as u64)
+                    .read_to_end(&mut head)
+                    .await?;
+
+                if sniff && !head.starts_with(&ELF_MAGIC) && !head.starts_with(b"#!") {
+                    tokio::io::copy(&mut input, &mut tokio::io::sink()).await?;
+                    return Ok(());
+                }
+
+                let staged = stage(head.as_slice().chain(input), &dest, mode).await?;
+                self.staged.push((staged, dest.clone()));
+                self.executables.push((rank, dest));
+            }
+            Place::Extra(dest) => {
+                if let Some(parent) = dest.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+
+                let staged = stage(input, &dest, 0o644).await?;
+                self.staged.push((staged, dest.clone()));
+                self.extras.push(dest);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Move the staged files into place and return them with executables ordered by their rank,
+    /// failing if there are none.
+    fn finish(self) -> Result<Extracted> {
+        let Self {
+            mut executables,
+            extras,
+            staged,
+        } = self;
+
+        if executables.is_empty() {
+            return Err(anyhow!("failed to find executable"));
+        }
+
+        for (staged, dest) in staged {
+            staged.persist(&dest)?;
+        }
+
+        executables.sort_by_key(|(rank, _)| *rank);
+
+        Ok(Extracted {
+            executables: executables.into_iter().map(|(_, path)| path).collect(),
+            extras,
+        })
+    }
+}
+
+/// Extract all executables chosen by `select` as well as shell completions and man pages into
+/// `dirs`. Entries without Unix permissions are considered executable if they are ELF binaries.
+pub(crate) async fn extract_zip<R: AsyncBufRead + AsyncSeek + Unpin>(
+    input: R,
+    dirs: &Dirs,
+    select: &Select,
+) -> Result<Extracted> {
+    let mut archive = ZipFileReader::with_tokio(input).await?;
+
+    let entries = archive
         .file()
         .entries()
         .iter()
         .enumerate()
-        .find_map(|(index, entry)| {
+        .filter_map(|(index, entry)| {
+            // Archives created without Unix permissions are read with empty ones.
             let mode = entry
                 .unix_permissions()
                 .map(u32::from)
-                .filter(|mode| (mode & 0o100) != 0)?;
+                .filter(|mode| *mode != 0);
 
             let name = entry
                 .filename()
@@ -49,39 +434,652 @@ pub(crate) async fn extract_zip<B: AsRef<[u8]> + Unpin>(
                 .ok()
                 .filter(|name| !name.ends_with('/'))?;
 
-            let basename = Path::new(name).file_name()?;
-            Some((index, mode, dest_dir.join(basename)))
+            Some((index, mode, PathBuf::from(name)))
         })
-        .ok_or_else(|| anyhow!("failed to find executable"))?;
+        .collect::<Vec<_>>();
+
+    let mut collected = Collected::default();
+
+    for (index, mode, path) in entries {
+        let mode = match mode {
+            Some(mode) => mode,
+            None => {
+                let mut magic = [0; 4];
+                let mut reader = archive.reader_without_entry(index).await?.compat();
+                let elf = reader.read_exact(&mut magic).await.is_ok() && magic == ELF_MAGIC;
+
+                if elf { 0o755 } else { 0o644 }
+            }
+        };
+
+        let executable = (mode & 0o100) != 0;
+
+        let Some(place) = collected.place(&path, executable, dirs, select, |_| true)? else {
+            continue;
+        };
 
-    let reader = archive.reader_without_entry(index).await?;
-    write_async(reader.compat(), &dest, mode).await?;
-    Ok(dest)
+        let reader = archive.reader_without_entry(index).await?;
+        collected.write(reader.compat(), place, mode).await?;
+    }
+
+    collected.finish()
 }
 
-/// Extract contained binary and return [`PathBuf`] to where it is located now.
+/// Extract all executables chosen by `select` as well as shell completions and man pages into
+/// `dirs`.
 pub(crate) async fn extract_tar<R: AsyncRead + Unpin>(
     input: R,
-    dest_dir: &Path,
-) -> Result<PathBuf> {
+    dirs: &Dirs,
+    select: &Select,
+) -> Result<Extracted> {
+    extract_tar_where(input, dirs, select, |_| true).await
+}
+
+/// Extract like [`extract_tar`] but only executables whose path is accepted by `accept`.
+async fn extract_tar_where<R: AsyncRead + Unpin>(
+    input: R,
+    dirs: &Dirs,
+    select: &Select,
+    accept: impl Fn(&Path) -> bool,
+) -> Result<Extracted> {
     let mut archive = tokio_tar::Archive::new(input);
     let mut entries = archive.entries()?;
+    let mut collected = Collected::default();
 
     while let Some(entry) = entries.next().await {
         let entry = entry?;
         let header = entry.header();
 
-        if let Ok(mode) = header.mode()
-            && (mode & 0o100) != 0
-            && header.entry_type() == tokio_tar::EntryType::Regular
-        {
-            let path = entry.path()?;
-            let name = path.file_name().ok_or_else(|| anyhow!("no filename"))?;
-            let dest = dest_dir.join(name);
-            write_async(entry, &dest, mode).await?;
-            return Ok(dest);
+        if header.entry_type() != tokio_tar::EntryType::Regular {
+            continue;
+        }
+
+        let mode = header.mode().unwrap_or(0o644);
+        let path = entry.path()?.into_owned();
+
+        if let Some(place) = collected.place(&path, (mode & 0o100) != 0, dirs, select, &accept)? {
+            collected.write(entry, place, mode).await?;
         }
     }
 
-    Err(anyhow!("failed to find executable"))
+    collected.finish()
+}
+
+/// Return `true` if a package installs `path` into a `bin` directory like `usr/bin`.
+fn in_bin_dir(path: &Path) -> bool {
+    path.parent().is_some_and(|parent| parent.ends_with("bin"))
+}
+
+/// Read and discard `count` bytes of `input`.
+async fn skip<R: AsyncRead + Unpin>(input: &mut R, count: u64) -> Result<()> {
+    let skipped = tokio::io::copy(&mut input.take(count), &mut tokio::io::sink()).await?;
+
+    if skipped != count {
+        return Err(anyhow!("unexpected end of archive"));
+    }
+
+    Ok(())
+}
+
+/// Decompress `input` according to the magic bytes it starts with, passing it through if it is not
+/// compressed.
+async fn decompress<'a, R: AsyncBufRead + Unpin + 'a>(
+    mut input: R,
+) -> Result<Box<dyn AsyncRead + Unpin + 'a>> {
+    let magic = input.fill_buf().await?;
+
+    if magic.starts_with(&[0x1f, 0x8b]) {
+        Ok(Box::new(GzipDecoder::new(input)))
+    } else if magic.starts_with(&[0xfd, b'7', b'z', b'X', b'Z', 0x00]) {
+        Ok(Box::new(XzDecoder::new(input)))
+    } else if magic.starts_with(&[0x28, 0xb5, 0x2f, 0xfd]) {
+        Ok(Box::new(ZstdDecoder::new(input)))
+    } else if magic.starts_with(b"BZh") {
+        Ok(Box::new(BzDecoder::new(input)))
+    } else if magic.starts_with(&[0x04, 0x22, 0x4d, 0x18]) {
+        Ok(Box::new(Lz4Decoder::new(input)))
+    } else {
+        Ok(Box::new(input))
+    }
+}
+
+/// Extract the executables chosen by `select` that the Debian package `input` installs into a
+/// `bin` directory as well as its shell completions and man pages into `dirs`.
+pub(crate) async fn extract_deb<R: AsyncRead + Unpin>(
+    mut input: R,
+    dirs: &Dirs,
+    select: &Select,
+) -> Result<Extracted> {
+    let mut magic = [0; 8];
+    input.read_exact(&mut magic).await?;
+
+    if &magic != b"!<arch>\n" {
+        return Err(anyhow!("not a Debian package"));
+    }
+
+    loop {
+        let mut header = [0; 60];
+
+        if let Err(err) = input.read_exact(&mut header).await {
+            return match err.kind() {
+                std::io::ErrorKind::UnexpectedEof => Err(anyhow!("package contains no data")),
+                _ => Err(err.into()),
+            };
+        }
+
+        let name = std::str::from_utf8(&header[..16])?.trim_end();
+        let size = std::str::from_utf8(&header[48..58])?
+            .trim()
+            .parse::<u64>()?;
+
+        if name.trim_end_matches('/').starts_with("data.tar") {
+            let data = tokio::io::BufReader::new((&mut input).take(size));
+            let data = decompress(data).await?;
+            return extract_tar_where(data, dirs, select, in_bin_dir).await;
+        }
+
+        // Members are aligned to even offsets.
+        skip(&mut input, size + size % 2).await?;
+    }
+}
+
+/// Skip the RPM header structure of `input`, optionally padded to a multiple of eight bytes.
+async fn skip_rpm_header<R: AsyncRead + Unpin>(input: &mut R, padded: bool) -> Result<()> {
+    let mut header = [0; 16];
+    input.read_exact(&mut header).await?;
+
+    if header[..3] != [0x8e, 0xad, 0xe8] {
+        return Err(anyhow!("invalid RPM header"));
+    }
+
+    let entries = u64::from(u32::from_be_bytes([
+        header[8], header[9], header[10], header[11],
+    ]));
+    let size = u64::from(u32::from_be_bytes([
+        header[12], header[13], header[14], header[15],
+    ]));
+    let size = entries * 16 + size;
+    let padding = if padded { (8 - size % 8) % 8 } else { 0 };
+
+    skip(input, size + padding).await
+}
+
+/// Extract the executables chosen by `select` that the RPM package `input` installs into a `bin`
+/// directory as well as its shell completions and man pages into `dirs`.
+pub(crate) async fn extract_rpm<R: AsyncRead + Unpin>(
+    mut input: R,
+    dirs: &Dirs,
+    select: &Select,
+) -> Result<Extracted> {
+    let mut lead = [0; 96];
+    input.read_exact(&mut lead).await?;
+
+    if lead[..4] != [0xed, 0xab, 0xee, 0xdb] {
+        return Err(anyhow!("not an RPM package"));
+    }
+
+    // Signature header followed by the main header.
+    skip_rpm_header(&mut input, true).await?;
+    skip_rpm_header(&mut input, false).await?;
+
+    let payload = decompress(tokio::io::BufReader::new(input)).await?;
+    extract_cpio(payload, dirs, select).await
+}
+
+/// Extract all executables chosen by `select` in a `bin` directory of the cpio archive `input` in
+/// "newc" format as well as shell completions and man pages into `dirs`.
+async fn extract_cpio<R: AsyncRead + Unpin>(
+    mut input: R,
+    dirs: &Dirs,
+    select: &Select,
+) -> Result<Extracted> {
+    let padding = |size: u64| (4 - size % 4) % 4;
+    let mut collected = Collected::default();
+
+    loop {
+        let mut header = [0; 110];
+        input.read_exact(&mut header).await?;
+
+        if &header[..6] != b"070701" && &header[..6] != b"070702" {
+            return Err(anyhow!("unsupported cpio format"));
+        }
+
+        let field = |index: usize| -> Result<u64> {
+            let start = 6 + index * 8;
+            let field = std::str::from_utf8(&header[start..start + 8])?;
+            Ok(u64::from_str_radix(field, 16)?)
+        };
+
+        let mode = u32::try_from(field(1)?)?;
+        let size = field(6)?;
+        let name_size = field(11)?;
+
+        let mut name = vec![0; usize::try_from(name_size)?];
+        input.read_exact(&mut name).await?;
+        skip(&mut input, padding(110 + name_size)).await?;
+
+        let name = String::from_utf8_lossy(&name);
+        let name = name.trim_end_matches('\0');
+
+        if name == "TRAILER!!!" {
+            return collected.finish();
+        }
+
+        let path = Path::new(name);
+        let regular = (mode & 0o170_000) == 0o100_000;
+        let place = if regular {
+            collected.place(path, (mode & 0o100) != 0, dirs, select, in_bin_dir)?
+        } else {
+            None
+        };
+
+        if let Some(place) = place {
+            collected
+                .write((&mut input).take(size), place, mode)
+                .await?;
+            skip(&mut input, padding(size)).await?;
+            continue;
+        }
+
+        skip(&mut input, size + padding(size)).await?;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use async_compression::tokio::write::GzipEncoder;
+    use tokio::io::AsyncWriteExt;
+
+    /// Build a tar archive with executables `usr/bin/bar` and `usr/bin/bar-helper`, executable
+    /// shared libraries and data, a non-executable `usr/share/doc/bar`, an executable bash and a
+    /// zsh completion and a man page.
+    async fn make_tar() -> Result<Vec<u8>> {
+        let mut builder = tokio_tar::Builder::new(Vec::new());
+
+        for (path, mode, content) in [
+            ("./usr/lib/baz", 0o755, &b"#!/bin/sh"[..]),
+            ("./usr/lib/libbar.so.1", 0o755, &ELF_MAGIC[..]),
+            ("./plugins/qux.so", 0o755, &ELF_MAGIC[..]),
+            ("./usr/bin/data", 0o755, &b"data"[..]),
+            ("./usr/share/doc/bar", 0o644, &b"doc"[..]),
+            ("./complete/bar.bash", 0o755, &b"#!/bin/bash"[..]),
+            ("./complete/_bar", 0o644, &b"#compdef bar"[..]),
+            ("./usr/share/man/man1/bar.1.gz", 0o644, &b"man"[..]),
+            ("./usr/bin/bar-helper", 0o755, &ELF_MAGIC[..]),
+            ("./usr/bin/bar", 0o755, &b"#!/bin/sh"[..]),
+        ] {
+            let mut header = tokio_tar::Header::new_gnu();
+            header.set_size(content.len() as u64);
+            header.set_mode(mode);
+            header.set_cksum();
+            builder.append_data(&mut header, path, content).await?;
+        }
+
+        Ok(builder.into_inner().await?)
+    }
+
+    /// Return [`Dirs`] for executables only.
+    fn bin_dirs(dir: &Path) -> Dirs {
+        Dirs {
+            bin: dir.to_path_buf(),
+            data: None,
+            taken: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn rank_main_executable() {
+        let select = Select::new(&[], &["rg", "ripgrep"]);
+        assert_eq!(select.name(), "rg");
+        assert_eq!(select.rank(Path::new("./ripgrep-14/ripgrep")), Some(0));
+        assert_eq!(select.rank(Path::new("RG")), Some(0));
+        assert_eq!(select.rank(Path::new("ripgrep-helper")), Some(1));
+        assert_eq!(select.rank(Path::new("bin/grep")), Some(2));
+
+        let select = Select::new(&["grep".into(), "rg*".into()], &["rg"]);
+        assert_eq!(select.rank(Path::new("bin/rgx")), Some(1));
+        assert_eq!(select.rank(Path::new("bin/ripgrep")), None);
+    }
+
+    #[test]
+    fn extra_paths() {
+        let extra = |path: &str| extra_path(Path::new(path));
+
+        assert_eq!(extra("doc/fd.1"), Some("man/man1/fd.1".into()));
+        assert_eq!(extra("fd-v10/doc/fd.5.gz"), Some("man/man5/fd.5.gz".into()));
+        assert_eq!(
+            extra("autocomplete/fd.bash"),
+            Some("bash-completion/completions/fd".into())
+        );
+        assert_eq!(
+            extra("completions/_fd"),
+            Some("zsh/site-functions/_fd".into())
+        );
+        assert_eq!(
+            extra("completions/fd.zsh"),
+            Some("zsh/site-functions/_fd".into())
+        );
+        assert_eq!(
+            extra("completions/fish/fd"),
+            Some("fish/vendor_completions.d/fd.fish".into())
+        );
+        assert_eq!(
+            extra("complete/fd.fish"),
+            Some("fish/vendor_completions.d/fd.fish".into())
+        );
+        assert_eq!(extra("fd.bash"), None);
+        assert_eq!(extra("completions/fd.ps1"), None);
+        assert_eq!(extra("libfoo.so.1"), None);
+        assert_eq!(extra("README.md"), None);
+    }
+
+    #[tokio::test]
+    async fn extract_all_executables() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let dir = temp.path();
+        let dirs = Dirs {
+            bin: dir.join("bin"),
+            data: Some(dir.join("share")),
+            taken: Vec::new(),
+        };
+        std::fs::create_dir_all(&dirs.bin)?;
+
+        let extracted = extract_tar(
+            make_tar().await?.as_slice(),
+            &dirs,
+            &Select::new(&[], &["bar"]),
+        )
+        .await?;
+
+        assert_eq!(
+            extracted.executables,
+            [dirs.bin.join("bar"), dirs.bin.join("bar-helper")]
+        );
+        assert_eq!(std::fs::read(dirs.bin.join("bar"))?, b"#!/bin/sh");
+        assert_eq!(
+            extracted.extras,
+            [
+                dir.join("share/bash-completion/completions/bar"),
+                dir.join("share/zsh/site-functions/_bar"),
+                dir.join("share/man/man1/bar.1.gz"),
+            ]
+        );
+
+        let dirs = bin_dirs(dir);
+        let select = Select::new(&["usr/lib/*".into(), "bar".into()], &["bar"]);
+        let extracted = extract_tar(make_tar().await?.as_slice(), &dirs, &select).await?;
+        assert_eq!(
+            extracted.executables,
+            [dir.join("baz"), dir.join("libbar.so.1"), dir.join("bar")]
+        );
+        assert!(extracted.extras.is_empty());
+
+        let select = Select::new(&["ba?".into()], &["bar"]);
+        let extracted = extract_tar(make_tar().await?.as_slice(), &dirs, &select).await?;
+        assert_eq!(extracted.executables.len(), 2);
+
+        let select = Select::new(&["bar*".into()], &["bar"]);
+        let extracted = extract_tar(make_tar().await?.as_slice(), &dirs, &select).await?;
+        assert!(!extracted.executables.contains(&dir.join("bar.bash")));
+
+        let select = Select::new(&["doc/*".into()], &["bar"]);
+        assert!(
+            extract_tar(make_tar().await?.as_slice(), &dirs, &select)
+                .await
+                .is_err()
+        );
+
+        let dirs = Dirs {
+            taken: vec![dir.join("bar")],
+            ..bin_dirs(dir)
+        };
+        assert!(
+            extract_tar(
+                make_tar().await?.as_slice(),
+                &dirs,
+                &Select::new(&[], &["bar"])
+            )
+            .await
+            .is_err()
+        );
+
+        let dirs = bin_dirs(dir);
+        std::fs::write(dir.join("bar-helper"), b"old")?;
+        let tar = make_tar().await?;
+        let truncated = &tar[..tar.len() - 1024 - 256];
+        assert!(
+            extract_tar(truncated, &dirs, &Select::new(&[], &["bar"]))
+                .await
+                .is_err()
+        );
+        assert_eq!(std::fs::read(dir.join("bar-helper"))?, b"old");
+        assert!(
+            std::fs::read_dir(dir)?
+                .filter_map(Result::ok)
+                .all(|entry| !entry.file_name().to_string_lossy().starts_with(".binge-"))
+        );
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn extract_spooled_zip() -> Result<()> {
+        use async_zip::base::write::ZipFileWriter;
+        use async_zip::{Compression, ZipEntryBuilder};
+
+        let mut writer = ZipFileWriter::new(Vec::new());
+
+        for (name, mode, content) in [
+            ("bar-1.0/README.md", 0o644, &b"readme"[..]),
+            ("bar-1.0/bar", 0o755, &b"#!bar"[..]),
+            ("bar-1.0/baz", 0o755, &b"#!baz"[..]),
+        ] {
+            let entry =
+                ZipEntryBuilder::new(name.into(), Compression::Deflate).unix_permissions(mode);
+            writer.write_entry_whole(entry, content).await?;
+        }
+
+        let zip = writer.close().await?;
+        let temp = tempfile::tempdir()?;
+        let dir = temp.path();
+        let file = spool(zip.as_slice(), dir).await?;
+        let extracted = extract_zip(file, &bin_dirs(dir), &Select::new(&[], &["bar"])).await?;
+
+        assert_eq!(extracted.executables, [dir.join("bar"), dir.join("baz")]);
+        assert_eq!(std::fs::read(dir.join("bar"))?, b"#!bar");
+        assert_eq!(std::fs::read_dir(dir)?.count(), 2);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn write_atomically() -> Result<()> {
+        let temp = tempfile::tempdir()?;
+        let dir = temp.path();
+        let dest = dir.join("bar");
+
+        write_async(&b"old"[..], &dest, 0o755).await?;
+        assert_eq!(std::fs::read(&dest)?, b"old");
+        assert_eq!(
+            std::fs::metadata(&dest)?.permissions().mode() & 0o777,
+            0o755
+        );
+
+        let broken = GzipDecoder::new(&b"\x1f\x8bbroken"[..]);
+        assert!(write_async(broken, &dest, 0o755).await.is_err());
+        assert_eq!(std::fs::read(&dest)?, b"old");
+
+        write_async(&b"new"[..], &dest, 0o644).await?;
+        assert_eq!(std::fs::read(&dest)?, b"new");
+        assert_eq!(
+            std::fs::metadata(&dest)?.permissions().mode() & 0o777,
+            0o644
+        );
+        assert_eq!(std::fs::read_dir(dir)?.count(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn extract_zip_without_permissions() -> Result<()> {
+        use async_zip::base::write::ZipFileWriter;
+        use async_zip::{Compression, ZipEntryBuilder};
+
+        let mut writer = ZipFileWriter::new(Vec::new());
+
+        for (name, content) in [
+            ("bar-1.0/bar", &[0x7f, b'E', b'L', b'F', 2, 1][..]),
+            ("bar-1.0/bar.txt", &b"text"[..]),
+        ] {
+            let entry = ZipEntryBuilder::new(name.into(), Compression::Deflate);
+            writer.write_entry_whole(entry, content).await?;
+        }
+
+        let zip = writer.close().await?;
+        let temp = tempfile::tempdir()?;
+        let dir = temp.path();
+        let file = spool(zip.as_slice(), dir).await?;
+        let extracted = extract_zip(file, &bin_dirs(dir), &Select::new(&[], &["bar"])).await?;
+
+        assert_eq!(extracted.executables, [dir.join("bar")]);
+        assert_eq!(
+            std::fs::metadata(dir.join("bar"))?.permissions().mode() & 0o777,
+            0o755
+        );
+        assert!(!dir.join("bar.txt").exists());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn decompress_by_magic() -> Result<()> {
+        use async_compression::tokio::write::{BzEncoder, Lz4Encoder, XzEncoder, ZstdEncoder};
+
+        async fn compressed<W: tokio::io::AsyncWrite + Unpin>(mut encoder: W) -> Result<W> {
+            encoder.write_all(b"bar").await?;
+            encoder.shutdown().await?;
+            Ok(encoder)
+        }
+
+        for data in [
+            compressed(GzipEncoder::new(Vec::new())).await?.into_inner(),
+            compressed(XzEncoder::new(Vec::new())).await?.into_inner(),
+            compressed(ZstdEncoder::new(Vec::new())).await?.into_inner(),
+            compressed(BzEncoder::new(Vec::new())).await?.into_inner(),
+            compressed(Lz4Encoder::new(Vec::new())).await?.into_inner(),
+            b"bar".to_vec(),
+        ] {
+            let mut output = Vec::new();
+            decompress(data.as_slice())
+                .await?
+                .read_to_end(&mut output)
+                .await?;
+            assert_eq!(output, b"bar");
+        }
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn extract_deb_package() -> Result<()> {
+        let mut encoder = GzipEncoder::new(Vec::new());
+        encoder.write_all(&make_tar().await?).await?;
+        encoder.shutdown().await?;
+        let data = encoder.into_inner();
+
+        let mut deb = b"!<arch>\n".to_vec();
+
+        for (name, content) in [
+            ("debian-binary", &b"2.0\n"[..]),
+            ("control.tar.gz", &b"odd"[..]),
+            ("data.tar.gz", &data),
+        ] {
+            deb.extend(
+                format!(
+                    "{name:<16}{:<12}{:<6}{:<6}{:<8}{:<10}`\n",
+                    0,
+                    0,
+                    0,
+                    644,
+                    content.len()
+                )
+                .bytes(),
+            );
+            deb.extend(content);
+
+            if content.len() % 2 == 1 {
+                deb.push(b'\n');
+            }
+        }
+
+        let temp = tempfile::tempdir()?;
+        let dir = temp.path();
+        let extracted =
+            extract_deb(deb.as_slice(), &bin_dirs(dir), &Select::new(&[], &["bar"])).await?;
+
+        assert_eq!(
+            extracted.executables,
+            [dir.join("bar"), dir.join("bar-helper")]
+        );
+        assert_eq!(std::fs::read(&extracted.executables[0])?, b"#!/bin/sh");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn extract_rpm_package() -> Result<()> {
+        let mut rpm = vec![0xed, 0xab, 0xee, 0xdb];
+        rpm.resize(96, 0);
+
+        // Signature header with one entry and five bytes of data, padded to eight bytes.
+        rpm.extend([0x8e, 0xad, 0xe8, 0x01, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 5]);
+        rpm.extend([0; 16 + 5 + 3]);
+
+        // Main header without entries.
+        rpm.extend([0x8e, 0xad, 0xe8, 0x01, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0]);
+
+        for (name, mode, content) in [
+            ("./usr/lib/baz", 0o100_755, &b"#!baz"[..]),
+            ("./usr/bin", 0o040_755, &b""[..]),
+            ("./usr/bin/bar", 0o100_755, &b"#!bar"[..]),
+            ("./usr/bin/barx", 0o100_755, &b"#!barx"[..]),
+            ("TRAILER!!!", 0, &b""[..]),
+        ] {
+            let header = format!(
+                "070701{:08x}{mode:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}{:08x}",
+                0,
+                0,
+                0,
+                1,
+                0,
+                content.len(),
+                0,
+                0,
+                0,
+                0,
+                name.len() + 1,
+                0
+            );
+
+            rpm.extend(header.bytes());
+            rpm.extend(name.bytes());
+            rpm.push(0);
+            rpm.resize(rpm.len().next_multiple_of(4), 0);
+            rpm.extend(content);
+            rpm.resize(rpm.len().next_multiple_of(4), 0);
+        }
+
+        let temp = tempfile::tempdir()?;
+        let dir = temp.path();
+        let paths = extract_rpm(rpm.as_slice(), &bin_dirs(dir), &Select::new(&[], &["barx"]))
+            .await?
+            .executables;
+
+        assert_eq!(paths, [dir.join("barx"), dir.join("bar")]);
+        assert_eq!(std::fs::read(&paths[0])?, b"#!barx");
+        assert_eq!(std::fs::read(&paths[1])?, b"#!bar");
+
+        Ok(())
+    }
 }
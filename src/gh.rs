@@ -1,4 +1,5 @@
-use std::path::{Path, PathBuf};
+use std::cmp::Ordering;
+use std::path::PathBuf;
 
 use anyhow::{Context, Result, anyhow};
 use futures_lite::{Stream, StreamExt};
@@ -6,15 +7,28 @@ use regex::Regex;
 use reqwest::Url;
 use reqwest::header::{self, HeaderMap, HeaderValue};
 use serde::Deserialize;
+use tokio::io::{AsyncBufRead, AsyncRead};
 use tokio::sync::mpsc::UnboundedSender;
 use tokio_util::bytes::Bytes;
 
-use crate::{Binary, Repo, extract};
+use jiff::{SignedDuration, Timestamp};
+use semver::VersionReq;
+
+use crate::extract::{self, Extracted};
+use crate::host::{Libc, Target};
+use crate::manifest::{Channel, Options};
+use crate::{Binary, Repo};
 
 /// API release.
 #[derive(Deserialize, Debug)]
 pub(crate) struct Release {
     pub tag_name: String,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+    pub published_at: Option<Timestamp>,
+    pub body: Option<String>,
     pub assets: Vec<Asset>,
 }
 
@@ -24,6 +38,56 @@ pub(crate) struct Asset {
     pub name: String,
     #[serde(rename = "browser_download_url")]
     pub url: String,
+    #[serde(default)]
+    pub size: u64,
+}
+
+/// Result of comparing an installed binary with its upstream release.
+pub(crate) enum Status {
+    /// Installed version is the latest release.
+    Current,
+    /// The release is newer than the installed version.
+    Outdated(Release),
+    /// The installed version is newer than the release, e.g. after the latest release was
+    /// re-tagged.
+    Ahead(Release),
+}
+
+/// Outcome of [`check`].
+pub(crate) struct Outcome {
+    /// Comparison of the installed version with the selected release.
+    pub status: Status,
+    /// Newer releases that were not selected.
+    pub excluded: Vec<Excluded>,
+}
+
+/// Newer release that was not selected.
+pub(crate) struct Excluded {
+    pub release: Release,
+    pub reason: Exclusion,
+}
+
+/// Reason for not selecting a newer release.
+pub(crate) enum Exclusion {
+    /// The release does not satisfy the version constraint.
+    Constraint,
+    /// The release is too recent and becomes eligible at the given time.
+    Cooldown(Timestamp),
+}
+
+/// Release picked for a binary.
+struct Selection {
+    /// Newest release satisfying the requirements.
+    release: Release,
+    /// Newer releases that were not selected.
+    excluded: Vec<Excluded>,
+}
+
+/// Minimum age releases must have before being selected.
+#[derive(Clone, Copy)]
+struct Cooldown {
+    now: Timestamp,
+    period: SignedDuration,
 }
 
 /// Supported compression type.
@@ -37,6 +101,10 @@ pub(crate) enum Compression {
     Zstd(Archive),
     /// Xz.
     Xz(Archive),
+    /// Bzip2.
+    Bz2(Archive),
+    /// LZ4 frame format.
+    Lz4(Archive),
 }
 
 /// Supported archive types.
@@ -48,6 +116,12 @@ pub(crate) enum Archive {
     Zip,
     /// Tape Archive.
     Tar,
+    /// Debian package.
+    Deb,
+    /// RPM package.
+    Rpm,
+    /// Self-contained AppImage executable.
+    AppImage,
 }
 
 /// Release file information.
@@ -55,7 +129,123 @@ pub(crate) enum Archive {
 pub(crate) struct File {
     url: Url,
     filename: PathBuf,
-    kind: Compression,
+    pub kind: Compression,
+    /// Whether the file name matches the architecture and OS.
+    arch_os: bool,
+}
+
+/// Reason for not considering an asset for installation.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum Rejection {
+    /// Download URL cannot be parsed.
+    Url,
+    /// Name does not mention the architecture and OS.
+    ArchOs,
+    /// Auxiliary file like a checksum or an editor extension.
+    Auxiliary,
+    /// Name does not match the requested asset pattern.
+    Pattern,
+}
+
+impl std::fmt::Display for Archive {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Archive::None => write!(f, "file"),
+            Archive::Zip => write!(f, "zip"),
+            Archive::Tar => write!(f, "tar"),
+            Archive::Deb => write!(f, "deb"),
+            Archive::Rpm => write!(f, "rpm"),
+            Archive::AppImage => write!(f, "appimage"),
+        }
+    }
+}
+
+impl std::fmt::Display for Compression {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Compression::None(archive) => write!(f, "{archive}"),
+            Compression::Gz(archive) => write!(f, "{archive}+gz"),
+            Compression::Zstd(archive) => write!(f, "{archive}+zst"),
+            Compression::Xz(archive) => write!(f, "{archive}+xz"),
+            Compression::Bz2(archive) => write!(f, "{archive}+bz2"),
+            Compression::Lz4(archive) => write!(f, "{archive}+lz4"),
+        }
+    }
+}
+
+impl std::fmt::Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Url => write!(f, "invalid download URL"),
+            Rejection::ArchOs => write!(f, "no arch/OS match"),
+            Rejection::Auxiliary => write!(f, "auxiliary file"),
+            Rejection::Pattern => write!(f, "no asset pattern match"),
+        }
+    }
+}
+
+/// Error returned if no asset of a release can be installed on a target.
+#[derive(Debug)]
+pub(crate) struct NoAsset {
+    /// Tag of the release.
+    pub tag: String,
+    /// Target assets were looked for.
+    pub target: Target,
+    /// Names of all assets with the reason they were rejected.
+    pub rejected: Vec<(String, Rejection)>,
+    /// Rejected assets that would be installed if picked explicitly.
+    pub offers: Vec<Offer>,
+}
+
+/// Rejected asset that is installed when selected by its derived pattern.
+#[derive(Debug)]
+pub(crate) struct Offer {
+    pub name: String,
+    /// Archive and compression detected from the name.
+    pub kind: Compression,
+    /// Whether the name matches the architecture and OS of the target.
+    pub arch_os: bool,
+    pub rejection: Rejection,
+    /// Pattern matching the asset in this and later releases.
+    pub pattern: String,
+}
+
+impl std::fmt::Display for NoAsset {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.rejected.is_empty() {
+            return write!(f, "release {} has no assets", self.tag);
+        }
+
+        write!(
+            f,
+            "no asset of release {} found for {}, candidates are:",
+            self.tag, self.target
+        )?;
+
+        for (name, rejection) in &self.rejected {
+            write!(f, "\n  {name} ({rejection})")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::error::Error for NoAsset {}
+
+/// Derive a pattern matching the asset `name` of release `tag` in later releases by replacing the
+/// version in the name with a wildcard.
+pub(crate) fn asset_pattern(name: &str, tag: &str) -> String {
+    let version = tag.trim_start_matches(|c: char| !c.is_ascii_digit());
+
+    let parts = [tag, version]
+        .into_iter()
+        .filter(|version| !version.is_empty())
+        .find(|version| name.contains(version))
+        .map_or_else(|| vec![name], |version| name.split(version).collect());
+
+    let parts = parts.into_iter().map(regex::escape).collect::<Vec<_>>();
+
+    format!("^{}$", parts.join(".+"))
 }
 
 /// Create a new client usable for GitHub APIs.
@@ -109,10 +299,10 @@ fn parse_archive(path: PathBuf) -> Archive {
         .into_string()
         .unwrap_or_default();
 
-    if extension.as_str() == "tar" {
-        Archive::Tar
-    } else {
-        Archive::None
+    match extension.as_str() {
+        "tar" => Archive::Tar,
+        "zip" => Archive::Zip,
+        _ => Archive::None,
     }
 }
 
@@ -135,38 +325,111 @@ fn parse_compression(mut path: PathBuf) -> Compression {
         "gz" => Compression::Gz(archive),
         "xz" => Compression::Xz(archive),
         "zst" => Compression::Zstd(archive),
+        "bz2" => Compression::Bz2(archive),
+        "lz4" => Compression::Lz4(archive),
+        "tgz" => Compression::Gz(Archive::Tar),
+        "txz" => Compression::Xz(Archive::Tar),
+        "tzst" => Compression::Zstd(Archive::Tar),
+        "tbz" | "tbz2" => Compression::Bz2(Archive::Tar),
         "zip" => Compression::None(Archive::Zip),
+        "deb" => Compression::None(Archive::Deb),
+        "rpm" => Compression::None(Archive::Rpm),
+        "appimage" => Compression::None(Archive::AppImage),
         _ => Compression::None(archive),
     }
 }
 
-/// Map to alternative architecture/OS conventions.
-fn alt_arch_os(arch: &'static str) -> &'static str {
-    if arch == "x86_64" {
-        "(x86_64|amd64|x64)"
-    } else {
-        arch
+/// Map to alternative architecture/OS conventions, i.e. a regex alternation of the names used in
+/// asset names for `arch` as reported by `std::env::consts::ARCH`. Endianness of MIPS and PowerPC
+/// follows the host.
+fn alt_arch_os(arch: &str) -> &str {
+    let little = cfg!(target_endian = "little");
+
+    match arch {
+        "x86_64" => "(x86_64|amd64|x64)",
+        "x86" => "(x86|i386|i486|i586|i686|386)",
+        "aarch64" => "(aarch64|arm64|armv8)",
+        "arm" => "(arm|armv7|armv7l|armv7hf|armhf|armv6|armv6l|armel)",
+        "riscv64" => "(riscv64|riscv64gc)",
+        "powerpc" => "(powerpc|ppc)",
+        "powerpc64" if little => "(powerpc64le|ppc64le)",
+        "powerpc64" => "(powerpc64|ppc64)",
+        "mips" if little => "(mipsel|mipsle)",
+        "mips64" if little => "(mips64el|mips64le)",
+        "loongarch64" => "(loongarch64|loong64)",
+        "sparc64" => "(sparc64|sparcv9)",
+        _ => arch,
     }
 }
 
-fn parse_file(filename: String, url: Url, arch: &'static str, os: &str) -> Option<File> {
+/// Return `true` if `filename` names `arch` and `os` in either order. The architecture must be
+/// followed by a separator so that `arm` does not match `arm64` and `x86` not `x86_64`.
+fn matches_arch_os(filename: &str, arch: &str, os: &str) -> bool {
     let arch = alt_arch_os(arch);
 
     let expr = Regex::new(&format!(
-        "^.*({arch}-[\\w\\d-]*{os}|[\\w\\d-]*{os}-{arch}).*$"
+        "^.*({arch}-[\\w\\d-]*{os}|[\\w\\d-]*{os}-{arch}(?:[-.]|$)).*$"
     ))
     .expect("compiling the regex");
 
-    let filename = PathBuf::from(filename);
+    expr.is_match(filename)
+}
+
+/// Architectures with aliases, used to tell whether a name mentions any architecture.
+const KNOWN_ARCHS: [&str; 12] = [
+    "x86_64",
+    "x86",
+    "aarch64",
+    "arm",
+    "riscv64",
+    "powerpc",
+    "powerpc64",
+    "mips",
+    "mips64",
+    "loongarch64",
+    "sparc64",
+    "s390x",
+];
+
+/// Return `true` if `filename` names `arch` as a separate word, e.g. `bar_1.0_amd64.deb`.
+fn matches_arch(filename: &str, arch: &str) -> bool {
+    let arch = alt_arch_os(arch);
+
+    Regex::new(&format!("(?:^|[-_.]){arch}(?:[-.]|$)"))
+        .expect("compiling the regex")
+        .is_match(filename)
+}
+
+/// Return `true` if `filename` of kind `kind` is built for `arch` and `os`. Packages only for Linux
+/// need to name just `arch`. AppImages not naming any architecture are assumed to be built for
+/// `x86_64`.
+fn matches_target(filename: &str, kind: &Compression, arch: &str, os: &str) -> bool {
+    if matches!(kind, Compression::None(Archive::AppImage)) && os == "linux" {
+        matches_arch(filename, arch)
+            || (arch == "x86_64" && !KNOWN_ARCHS.iter().any(|arch| matches_arch(filename, arch)))
+    } else if kind.is_linux_only() && os == "linux" {
+        matches_arch(filename, arch)
+    } else {
+        matches_arch_os(filename, arch, os)
+    }
+}
 
-    expr.find(filename.to_str()?)?;
+/// Parse `filename` into a [`File`] if it is built for `arch` and `os` according to
+/// [`matches_target`].
+fn parse_file(filename: String, url: Url, arch: &str, os: &str) -> Option<File> {
+    let kind = parse_compression(PathBuf::from(&filename));
 
-    let kind = parse_compression(filename.clone());
+    if !matches_target(&filename, &kind, arch, os) {
+        return None;
+    }
+
+    let filename = PathBuf::from(filename);
 
     Some(File {
         url,
         filename,
         kind,
+        arch_os: true,
     })
 }
 
@@ -196,173 +459,801 @@ fn report_progress(
         })
 }
 
-async fn fetch_and_extract(
-    dest_dir: &Path,
-    assets: Vec<Asset>,
-    progress: UnboundedSender<f64>,
-) -> Result<PathBuf> {
-    let mut candidates = assets
-        .into_iter()
-        .filter_map(
-            |Asset {
-                 name,
-                 url: browser_download_url,
-             }| {
-                let url: Url = browser_download_url.parse().ok()?;
-                parse_file(name, url, std::env::consts::ARCH, std::env::consts::OS)
-            },
+impl Compression {
+    /// Return the archive contained in the possibly compressed asset.
+    fn archive(&self) -> &Archive {
+        match self {
+            Compression::None(archive)
+            | Compression::Gz(archive)
+            | Compression::Zstd(archive)
+            | Compression::Xz(archive)
+            | Compression::Bz2(archive)
+            | Compression::Lz4(archive) => archive,
+        }
+    }
+
+    /// Returns `true` if assets of this kind can be extracted. Packages and AppImages are only
+    /// installed uncompressed.
+    fn is_supported(&self) -> bool {
+        match self {
+            Compression::None(_) => true,
+            compressed => matches!(
+                compressed.archive(),
+                Archive::None | Archive::Tar | Archive::Zip
+            ),
+        }
+    }
+
+    /// Returns `true` if assets of this kind only exist for Linux and thus do not mention the OS in
+    /// their names.
+    fn is_linux_only(&self) -> bool {
+        matches!(
+            self,
+            Compression::None(Archive::Deb | Archive::Rpm | Archive::AppImage)
         )
-        .filter(|f| {
-            f.filename
-                .extension()
-                .map(|ext| ext != "vsix" && ext != "sha256")
-                .unwrap_or(true)
-        });
+    }
+}
 
-    if let Some(candidate) = candidates.next() {
-        let response = make_download_client()?.get(candidate.url).send().await?;
-        let bytes = report_progress(response, &progress);
+/// Extensions of checksums, signatures, metadata and editor extensions that are never installed.
+const AUXILIARY_EXTENSIONS: [&str; 12] = [
+    "vsix", "sha256", "sha512", "sha1", "md5", "sig", "asc", "pem", "sbom", "spdx", "json", "txt",
+];
+
+/// Name tokens hinting at debug builds, debug symbols or source code.
+const AUXILIARY_TOKENS: [&str; 8] = [
+    "debug", "dbg", "symbols", "sym", "pdb", "src", "source", "sources",
+];
+
+/// Name tokens of ARM builds using hardware floating point, which any current 32-bit ARM Linux
+/// runs, as opposed to soft-float ones.
+const HARD_FLOAT_TOKENS: [&str; 6] = [
+    "armhf",
+    "armv7",
+    "armv7l",
+    "armv7hf",
+    "gnueabihf",
+    "musleabihf",
+];
+
+/// Name tokens of soft-float or ARMv6 builds, which run on hard-float systems but slower.
+const SOFT_FLOAT_TOKENS: [&str; 5] = ["armel", "armv6", "armv6l", "gnueabi", "musleabi"];
+
+/// Score `file` for installation on `arch`, higher is better. Builds for the host `libc` are
+/// preferred, followed by statically linked ones.
+fn score(file: &File, arch: &str, libc: Option<Libc>) -> i32 {
+    let name = file.filename.to_string_lossy().to_ascii_lowercase();
+
+    let tokens = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .collect::<Vec<_>>();
+
+    let has = |token: &str| tokens.contains(&token);
+
+    let mut score = 0;
+
+    if file.arch_os {
+        score += 100;
+    }
 
-        let path = match candidate.kind {
-            Compression::None(Archive::Zip) => {
-                let mut buffer = Vec::new();
-                let mut bytes = std::pin::pin!(bytes);
+    if name.contains(arch) {
+        score += 10;
+    }
 
-                while let Some(chunk) = bytes.next().await {
-                    buffer.extend_from_slice(&chunk?);
-                }
+    if file.kind.is_supported() {
+        score += 10;
+    } else {
+        score -= 50;
+    }
+
+    if AUXILIARY_TOKENS.iter().any(|token| has(token)) {
+        score -= 80;
+    }
+
+    if arch == "arm" {
+        if HARD_FLOAT_TOKENS.iter().any(|token| has(token)) {
+            score += 4;
+        } else if SOFT_FLOAT_TOKENS.iter().any(|token| has(token)) {
+            score -= 4;
+        }
+    }
 
-                extract::extract_zip(buffer, dest_dir).await?
+    if file.kind.is_linux_only() {
+        score -= 5;
+    }
+
+    let is_static = has("static");
+    let is_musl = has("musl");
+    let is_gnu = has("gnu") || has("glibc");
+
+    match libc {
+        Some(Libc::Musl) => {
+            if is_musl {
+                score += 20;
+            } else if is_static {
+                score += 15;
+            } else if is_gnu {
+                score -= 20;
+            }
+        }
+        Some(Libc::Gnu) => {
+            if is_gnu {
+                score += 5;
+            } else if is_static {
+                score += 3;
+            } else if is_musl {
+                score += 2;
             }
-            Compression::None(Archive::Tar) => {
-                let read = std::pin::pin!(stream_to_reader(bytes));
-                extract::extract_tar(read, dest_dir).await?
+        }
+        None => {
+            if is_static {
+                score += 5;
+            } else if is_musl {
+                score += 2;
             }
-            Compression::Gz(archive) => {
-                let read = stream_to_reader(bytes);
-                let read = tokio::io::BufReader::new(read);
-                let input = async_compression::tokio::bufread::GzipDecoder::new(read);
-
-                match archive {
-                    Archive::None => {
-                        let name = candidate
-                            .filename
-                            .file_name()
-                            .ok_or_else(|| anyhow!("asset has no usable file name"))?;
-                        let path = dest_dir.join(name);
-                        extract::write_async(input, &path, 0o755).await?;
-                        path
+        }
+    }
+
+    score
+}
+
+/// Sort `candidates` from best to worst for installation on `arch`. Equally scored candidates are
+/// ordered by name length and then lexicographically to not depend on the API's asset order.
+fn rank(candidates: &mut [File], arch: &str, libc: Option<Libc>) {
+    candidates.sort_by(|a, b| {
+        score(b, arch, libc)
+            .cmp(&score(a, arch, libc))
+            .then_with(|| {
+                a.filename
+                    .as_os_str()
+                    .len()
+                    .cmp(&b.filename.as_os_str().len())
+            })
+            .then_with(|| a.filename.cmp(&b.filename))
+    });
+}
+
+/// Classify `asset` for installation on `arch` and `os`. If a `pattern` is given, the asset name
+/// must match it but is accepted even without mentioning architecture and OS.
+fn classify(
+    asset: &Asset,
+    arch: &str,
+    os: &str,
+    pattern: Option<&Regex>,
+) -> Result<File, Rejection> {
+    let url: Url = asset.url.parse().map_err(|_| Rejection::Url)?;
+
+    if pattern.is_some_and(|pattern| !pattern.is_match(&asset.name)) {
+        return Err(Rejection::Pattern);
+    }
+
+    let file = match parse_file(asset.name.clone(), url.clone(), arch, os) {
+        Some(file) => file,
+        None if pattern.is_some() => File {
+            url,
+            filename: PathBuf::from(&asset.name),
+            kind: parse_compression(PathBuf::from(&asset.name)),
+            arch_os: false,
+        },
+        None => return Err(Rejection::ArchOs),
+    };
+
+    let auxiliary = file
+        .filename
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| AUXILIARY_EXTENSIONS.contains(&ext.to_ascii_lowercase().as_str()));
+
+    if auxiliary {
+        return Err(Rejection::Auxiliary);
+    }
+
+    Ok(file)
+}
+
+/// Classify `assets` for `target`, optionally restricted to names matching `pattern`. Returns the
+/// candidates ranked from best to worst and the names of rejected assets.
+fn choose(
+    assets: &[Asset],
+    pattern: Option<&Regex>,
+    target: &Target,
+) -> (Vec<File>, Vec<(String, Rejection)>) {
+    let mut candidates = Vec::new();
+    let mut rejected = Vec::new();
+
+    for asset in assets {
+        match classify(asset, &target.arch, &target.os, pattern) {
+            Ok(file) => candidates.push(file),
+            Err(rejection) => rejected.push((asset.name.clone(), rejection)),
+        }
+    }
+
+    rank(&mut candidates, &target.arch, target.libc);
+
+    (candidates, rejected)
+}
+
+/// Return the `rejected` assets of release `tag` that would be chosen for `target` when selected by
+/// the pattern derived from their name.
+fn offers(
+    assets: &[Asset],
+    rejected: &[(String, Rejection)],
+    tag: &str,
+    target: &Target,
+) -> Vec<Offer> {
+    rejected
+        .iter()
+        .filter_map(|(name, rejection)| {
+            let pattern = asset_pattern(name, tag);
+            let (candidates, _) = choose(assets, Some(&Regex::new(&pattern).ok()?), target);
+
+            candidates
+                .first()
+                .is_some_and(|file| {
+                    file.filename.as_os_str() == name.as_str() && file.kind.is_supported()
+                })
+                .then(|| {
+                    let kind = parse_compression(PathBuf::from(name));
+
+                    Offer {
+                        name: name.clone(),
+                        arch_os: matches_target(name, &kind, &target.arch, &target.os),
+                        kind,
+                        rejection: *rejection,
+                        pattern,
                     }
-                    Archive::Zip => todo!(),
-                    Archive::Tar => extract::extract_tar(input, dest_dir).await?,
-                }
+                })
+        })
+        .collect()
+}
+
+/// How an asset was judged during selection.
+pub(crate) struct Verdict {
+    pub name: String,
+    /// Whether the name matches the architecture and OS of the target.
+    pub arch_os: bool,
+    /// Archive and compression detected from the name.
+    pub kind: Compression,
+    /// Size in bytes.
+    pub size: u64,
+    /// Score of the candidate or the reason it was rejected.
+    pub outcome: Result<i32, Rejection>,
+    /// Whether the asset would be installed.
+    pub chosen: bool,
+}
+
+/// Asset selection of a release for a target.
+pub(crate) struct Explanation {
+    pub tag: String,
+    pub target: Target,
+    pub verdicts: Vec<Verdict>,
+}
+
+/// Judge all assets of `release` for `target` like [`fetch_and_extract`] would.
+pub(crate) fn explain_release(
+    release: Release,
+    pattern: Option<&str>,
+    target: &Target,
+) -> Result<Explanation> {
+    let pattern = pattern.map(Regex::new).transpose()?;
+    let (candidates, _) = choose(&release.assets, pattern.as_ref(), target);
+    let chosen = candidates.first().map(|file| file.filename.as_os_str());
+
+    let verdicts = release
+        .assets
+        .iter()
+        .map(|asset| {
+            let kind = parse_compression(PathBuf::from(&asset.name));
+
+            Verdict {
+                name: asset.name.clone(),
+                arch_os: matches_target(&asset.name, &kind, &target.arch, &target.os),
+                kind,
+                size: asset.size,
+                outcome: classify(asset, &target.arch, &target.os, pattern.as_ref())
+                    .map(|file| score(&file, &target.arch, target.libc)),
+                chosen: chosen.is_some_and(|chosen| chosen == asset.name.as_str()),
             }
-            Compression::Zstd(Archive::Tar) => {
-                let read = stream_to_reader(bytes);
-                let read = tokio::io::BufReader::new(read);
-                let input = async_compression::tokio::bufread::ZstdDecoder::new(read);
-                extract::extract_tar(input, dest_dir).await?
+        })
+        .collect();
+
+    Ok(Explanation {
+        tag: release.tag_name,
+        target: target.clone(),
+        verdicts,
+    })
+}
+
+/// Download the best matching asset of release `tag` for `target`, optionally restricted to names
+/// matching `pattern`, and extract the executables chosen by `select` as well as shell completions
+/// and man pages into `dirs`. AppImages are stored under the name of the main executable. Fails
+/// with [`NoAsset`] if none matches.
+async fn fetch_and_extract(
+    dirs: &extract::Dirs,
+    tag: &str,
+    assets: Vec<Asset>,
+    pattern: Option<&str>,
+    target: &Target,
+    select: &extract::Select,
+    progress: UnboundedSender<f64>,
+) -> Result<Extracted> {
+    let pattern = pattern.map(Regex::new).transpose()?;
+    let (candidates, rejected) = choose(&assets, pattern.as_ref(), target);
+
+    if let Some(candidate) = candidates.into_iter().next() {
+        if !candidate.kind.is_supported() {
+            return Err(anyhow!(
+                "{} asset {:?} is not supported",
+                candidate.kind,
+                candidate.filename
+            ));
+        }
+
+        let response = make_download_client()?.get(candidate.url).send().await?;
+        let bytes = report_progress(response, &progress);
+
+        let read = tokio::io::BufReader::new(stream_to_reader(bytes));
+        let input = decoder(&candidate.kind, read);
+
+        let extracted = match candidate.kind.archive() {
+            Archive::Zip => {
+                let file = extract::spool(input, &dirs.bin).await?;
+                extract::extract_zip(file, dirs, select).await?
             }
-            Compression::Xz(Archive::Tar) => {
-                let read = stream_to_reader(bytes);
-                let read = tokio::io::BufReader::new(read);
-                let input = async_compression::tokio::bufread::XzDecoder::new(read);
-                extract::extract_tar(input, dest_dir).await?
+            Archive::Tar => extract::extract_tar(input, dirs, select).await?,
+            Archive::Deb => extract::extract_deb(input, dirs, select).await?,
+            Archive::Rpm => extract::extract_rpm(input, dirs, select).await?,
+            Archive::AppImage => {
+                let path = dirs.bin.join(select.name());
+                dirs.ensure_free(&path)?;
+                extract::write_async(input, &path, 0o755).await?;
+                Extracted::single(path)
             }
-            Compression::None(Archive::None) => {
-                let read = std::pin::pin!(stream_to_reader(bytes));
-                let name = candidate
-                    .filename
-                    .file_name()
-                    .ok_or_else(|| anyhow!("asset has no usable file name"))?;
-                let path = dest_dir.join(name);
-                extract::write_async(read, &path, 0o755).await?;
-                path
+            Archive::None => {
+                // Strip the compression extension off compressed single files.
+                let name = match candidate.kind {
+                    Compression::None(_) => candidate.filename.file_name(),
+                    _ => candidate.filename.file_stem(),
+                }
+                .ok_or_else(|| anyhow!("asset has no usable file name"))?;
+
+                let path = dirs.bin.join(name);
+                dirs.ensure_free(&path)?;
+                extract::write_async(input, &path, 0o755).await?;
+                Extracted::single(path)
             }
-            missing => todo!("{missing:?}"),
         };
 
-        return Ok(path);
+        return Ok(extracted);
+    }
+
+    Err(NoAsset {
+        tag: tag.to_string(),
+        target: target.clone(),
+        offers: offers(&assets, &rejected, tag, target),
+        rejected,
     }
+    .into())
+}
+
+/// Wrap `input` in a decoder for the compression of `kind`.
+fn decoder<'a, R: AsyncBufRead + Unpin + 'a>(
+    kind: &Compression,
+    input: R,
+) -> Box<dyn AsyncRead + Unpin + 'a> {
+    use async_compression::tokio::bufread::{
+        BzDecoder, GzipDecoder, Lz4Decoder, XzDecoder, ZstdDecoder,
+    };
 
-    Err(anyhow!("no asset found"))
+    match kind {
+        Compression::None(_) => Box::new(input),
+        Compression::Gz(_) => Box::new(GzipDecoder::new(input)),
+        Compression::Zstd(_) => Box::new(ZstdDecoder::new(input)),
+        Compression::Xz(_) => Box::new(XzDecoder::new(input)),
+        Compression::Bz2(_) => Box::new(BzDecoder::new(input)),
+        Compression::Lz4(_) => Box::new(Lz4Decoder::new(input)),
+    }
 }
 
-fn stream_to_reader(
-    stream: impl Stream<Item = reqwest::Result<Bytes>>,
-) -> impl tokio::io::AsyncRead + Unpin {
+fn stream_to_reader(stream: impl Stream<Item = reqwest::Result<Bytes>>) -> impl AsyncRead + Unpin {
     let stream = stream.then(|b| async { b.map_err(std::io::Error::other) });
 
     Box::pin(tokio_util::io::StreamReader::new(stream))
 }
 
-/// Install latest version and record in the local installation manifest.
+/// Send a GET request to `url` and deserialize the JSON response.
+async fn get_json<T: serde::de::DeserializeOwned>(client: &reqwest::Client, url: Url) -> Result<T> {
+    Ok(client
+        .get(url)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?)
+}
+
+/// Pick the newest of `releases`, which are expected to be ordered newest first as returned by the
+/// API. Releases whose tags cannot be compared keep that order.
+fn newest(releases: impl IntoIterator<Item = Release>) -> Option<Release> {
+    releases.into_iter().reduce(|newest, release| {
+        match crate::version::compare(&newest.tag_name, &release.tag_name) {
+            Some(Ordering::Less) => release,
+            _ => newest,
+        }
+    })
+}
+
+/// Filter `releases` down to those on the channel and matching the tag pattern of `options`.
+fn followed(
+    releases: Vec<Release>,
+    options: &Options,
+) -> Result<impl Iterator<Item = Release> + use<'_>> {
+    let pattern = options.tag_pattern.as_deref().map(Regex::new).transpose()?;
+
+    Ok(releases.into_iter().filter(move |release| {
+        !release.draft
+            && (options.channel == Channel::Prerelease || !release.prerelease)
+            && pattern
+                .as_ref()
+                .is_none_or(|pattern| pattern.is_match(&release.tag_name))
+    }))
+}
+
+impl Cooldown {
+    /// Create a cooldown of `days` starting from now or `None` if `days` is zero.
+    fn new(days: u32) -> Option<Self> {
+        (days > 0).then(|| Self {
+            now: Timestamp::now(),
+            period: SignedDuration::from_hours(i64::from(days) * 24),
+        })
+    }
+
+    /// Time at which `release` becomes eligible or `None` if its publication time is unknown.
+    fn eligible_at(&self, release: &Release) -> Option<Timestamp> {
+        let published_at = release.published_at?;
+        Some(
+            published_at
+                .checked_add(self.period)
+                .unwrap_or(Timestamp::MAX),
+        )
+    }
+}
+
+/// Returns `true` if `excluded` is newer than the selected `release`. Tags that cannot be compared
+/// are considered newer because excluded releases are never older in terms of publication.
+fn is_newer(release: &Release, excluded: &Release) -> bool {
+    !matches!(
+        crate::version::compare(&release.tag_name, &excluded.tag_name),
+        Some(Ordering::Greater | Ordering::Equal)
+    )
+}
+
+/// Select the newest release from `releases` that is on the channel and matches the tag pattern
+/// of `options`, satisfies `constraint` and is older than the `cooldown` period.
+fn select(
+    releases: Vec<Release>,
+    options: &Options,
+    constraint: Option<&VersionReq>,
+    cooldown: Option<Cooldown>,
+) -> Result<Selection> {
+    let mut cooling = Vec::new();
+    let mut rejected = Vec::new();
+    let mut matching = Vec::new();
+
+    for release in followed(releases, options)? {
+        let eligible_at = cooldown.and_then(|cooldown| cooldown.eligible_at(&release));
+
+        if let (Some(cooldown), Some(eligible_at)) = (cooldown, eligible_at)
+            && eligible_at > cooldown.now
+        {
+            cooling.push(Excluded {
+                release,
+                reason: Exclusion::Cooldown(eligible_at),
+            });
+        } else if constraint.is_some_and(|constraint| {
+            !crate::version::parse(&release.tag_name)
+                .is_some_and(|version| constraint.matches(&version))
+        }) {
+            rejected.push(release);
+        } else {
+            matching.push(release);
+        }
+    }
+
+    let release = newest(matching).ok_or_else(|| match constraint {
+        Some(constraint) => anyhow!("no eligible release found matching constraint {constraint}"),
+        None => anyhow!("no eligible releases found"),
+    })?;
+
+    let mut excluded = cooling
+        .into_iter()
+        .filter(|excluded| is_newer(&release, &excluded.release))
+        .collect::<Vec<_>>();
+
+    if let Some(rejected) = newest(rejected).filter(|rejected| is_newer(&release, rejected)) {
+        excluded.push(Excluded {
+            release: rejected,
+            reason: Exclusion::Constraint,
+        });
+    }
+
+    Ok(Selection { release, excluded })
+}
+
+/// Fetch the release `repo` refers to, i.e. the one tagged with the requested tag or the newest
+/// one following `options`, satisfying the version constraint and older than `cooldown_days`.
+async fn fetch_release(
+    client: &reqwest::Client,
+    repo: &Repo,
+    options: &Options,
+    cooldown_days: u32,
+) -> Result<Selection> {
+    let base = format!(
+        "https://api.github.com/repos/{}/{}/releases",
+        repo.owner, repo.name
+    );
+
+    if let Some(tag) = &repo.tag {
+        let url = Url::parse(&format!("{base}/tags/{tag}"))?;
+        return Ok(Selection {
+            release: get_json(client, url).await?,
+            excluded: Vec::new(),
+        });
+    }
+
+    let cooldown = Cooldown::new(cooldown_days);
+
+    if options.channel == Channel::Stable
+        && options.tag_pattern.is_none()
+        && repo.constraint.is_none()
+        && cooldown.is_none()
+    {
+        let url = Url::parse(&format!("{base}/latest"))?;
+        return Ok(Selection {
+            release: get_json(client, url).await?,
+            excluded: Vec::new(),
+        });
+    }
+
+    let releases = get_json(client, Url::parse(&format!("{base}?per_page=100"))?).await?;
+    select(releases, options, repo.constraint.as_ref(), cooldown)
+}
+
+/// Explain which asset of the release `repo` refers to would be installed for `target`.
+pub(crate) async fn explain(
+    client: reqwest::Client,
+    repo: &Repo,
+    options: &Options,
+    cooldown_days: u32,
+    target: &Target,
+) -> Result<Explanation> {
+    let release = fetch_release(&client, repo, options, cooldown_days)
+        .await?
+        .release;
+
+    explain_release(release, options.asset.as_deref(), target)
+}
+
+/// Fetch the requested or latest release of `repo`.
+pub(crate) async fn release(client: reqwest::Client, repo: &Repo) -> Result<Release> {
+    Ok(fetch_release(&client, repo, &Options::default(), 0)
+        .await?
+        .release)
+}
+
+/// Fetch up to `count` most recent releases of `repo`.
+pub(crate) async fn releases(
+    client: reqwest::Client,
+    repo: &Repo,
+    count: usize,
+) -> Result<Vec<Release>> {
+    let url = Url::parse(&format!(
+        "https://api.github.com/repos/{}/{}/releases?per_page={count}",
+        repo.owner, repo.name
+    ))?;
+
+    get_json(&client, url).await
+}
+
+/// Install latest or requested version for `target` and record in the local installation manifest.
+/// Releases younger than `cooldown_days` are skipped.
 pub(crate) async fn install(
     client: reqwest::Client,
     repo: Repo,
-    dest_dir: &Path,
+    options: Options,
+    cooldown_days: u32,
+    target: &Target,
+    dirs: &extract::Dirs,
     progress: UnboundedSender<f64>,
 ) -> Result<Binary> {
-    let url = reqwest::Url::parse(&format!(
-        "https://api.github.com/repos/{}/{}/releases/latest",
-        repo.owner, repo.name,
-    ))?;
-    let Release { tag_name, assets } = client.get(url).send().await?.json().await?;
-    let mut path = fetch_and_extract(dest_dir, assets, progress).await?;
-
     if let Some(name) = &repo.rename {
-        let from = path.clone();
-        path.pop();
-        path.push(name);
+        dirs.ensure_free(&dirs.bin.join(name))?;
+    }
+
+    let Release {
+        tag_name, assets, ..
+    } = fetch_release(&client, &repo, &options, cooldown_days)
+        .await?
+        .release;
+    let Extracted {
+        executables: mut paths,
+        extras,
+    } = fetch_and_extract(
+        dirs,
+        &tag_name,
+        assets,
+        options.asset.as_deref(),
+        target,
+        &extract::Select::new(
+            &options.bin,
+            &[repo.rename.as_deref().unwrap_or(&repo.name), &repo.name],
+        ),
+        progress,
+    )
+    .await?;
 
-        std::fs::rename(from, &path)?;
+    if let Some(name) = &repo.rename {
+        rename_main(&mut paths, name)?;
     }
 
     Ok(Binary {
         repo,
-        path,
+        paths,
+        extras,
         version: tag_name,
+        options,
+        held: false,
+        retained: Vec::new(),
+    })
+}
+
+/// Rename the main executable, i.e. the first of `paths`, to `name`.
+fn rename_main(paths: &mut [PathBuf], name: &str) -> Result<()> {
+    if let Some(path) = paths.first_mut() {
+        let from = path.clone();
+        path.set_file_name(name);
+        std::fs::rename(from, path)?;
+    }
+
+    Ok(())
+}
+
+/// Compare the installed `version` with the `release` tag. Tags that cannot be interpreted as
+/// versions are considered outdated as soon as they differ.
+fn status(version: &str, release: Release) -> Status {
+    if version == release.tag_name {
+        return Status::Current;
+    }
+
+    match crate::version::compare(version, &release.tag_name) {
+        Some(Ordering::Less) | None => Status::Outdated(release),
+        Some(Ordering::Equal) => Status::Current,
+        Some(Ordering::Greater) => Status::Ahead(release),
+    }
+}
+
+/// Check if there is a new [`Release`] for `binary` that is older than `cooldown_days`.
+pub(crate) async fn check(
+    client: reqwest::Client,
+    binary: &Binary,
+    cooldown_days: u32,
+) -> Result<Outcome> {
+    let Selection { release, excluded } =
+        fetch_release(&client, &binary.repo, &binary.options, cooldown_days).await?;
+
+    Ok(Outcome {
+        status: status(&binary.version, release),
+        excluded,
     })
 }
 
-/// Check if there is a new [`Release`] for `binary`.
-pub(crate) async fn check(client: reqwest::Client, binary: &Binary) -> Result<Option<Release>> {
-    let url = reqwest::Url::parse(&format!(
-        "https://api.github.com/repos/{}/{}/releases/latest",
-        binary.repo.owner, binary.repo.name,
+/// Keep the releases newer than `installed` up to and including `target`. If the tags cannot be
+/// compared as versions, rely on the newest first order of `releases` instead.
+fn between(
+    releases: impl IntoIterator<Item = Release>,
+    installed: &str,
+    target: &str,
+) -> Vec<Release> {
+    use crate::version::parse;
+
+    if let (Some(installed), Some(target)) = (parse(installed), parse(target)) {
+        return releases
+            .into_iter()
+            .filter(|release| {
+                parse(&release.tag_name)
+                    .is_some_and(|version| version > installed && version <= target)
+            })
+            .collect();
+    }
+
+    releases
+        .into_iter()
+        .skip_while(|release| release.tag_name != target)
+        .take_while(|release| release.tag_name != installed)
+        .collect()
+}
+
+/// Fetch all releases of `binary` newer than the installed version up to and including `target`,
+/// newest first.
+pub(crate) async fn notes(
+    client: reqwest::Client,
+    binary: &Binary,
+    target: &str,
+) -> Result<Vec<Release>> {
+    let url = Url::parse(&format!(
+        "https://api.github.com/repos/{}/{}/releases?per_page=100",
+        binary.repo.owner, binary.repo.name
     ))?;
 
-    let release: Release = client.get(url).send().await?.json().await?;
-    Ok((binary.version != release.tag_name).then_some(release))
+    let releases = get_json(&client, url).await?;
+    let releases = followed(releases, &binary.options)?;
+
+    Ok(between(releases, &binary.version, target))
 }
 
-/// Try to update `binary` with `release` info. Returns `Ok(binary)` on successful update.
+/// Try to update `binary` with `release` info for `target`, installing shell completions and man
+/// pages into `data_dir` if given and never overwriting the `taken` paths of other binaries.
+/// Returns `Ok(binary)` on successful update.
 pub(crate) async fn update(
     binary: &Binary,
-    Release { tag_name, assets }: Release,
+    Release {
+        tag_name, assets, ..
+    }: Release,
+    target: &Target,
+    data_dir: Option<PathBuf>,
+    taken: Vec<PathBuf>,
     progress: UnboundedSender<f64>,
 ) -> Result<Binary> {
-    let dest_dir = &binary
-        .path
-        .parent()
-        .ok_or_else(|| anyhow!("no parent for path found"))?;
-
-    let mut path = fetch_and_extract(dest_dir, assets, progress)
-        .await
-        .with_context(|| "failed to extract".to_string())?;
-
-    if let Some(name) = &binary.repo.rename {
-        let from = path.clone();
-        path.pop();
-        path.push(name);
+    let main = binary
+        .paths
+        .first()
+        .ok_or_else(|| anyhow!("no installed executable recorded"))?;
+
+    let dirs = extract::Dirs {
+        bin: main
+            .parent()
+            .ok_or_else(|| anyhow!("no parent for path found"))?
+            .to_path_buf(),
+        data: data_dir,
+        taken,
+    };
 
-        std::fs::rename(from, &path)?;
+    let main_name = main
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or(&binary.repo.name);
+
+    let Extracted {
+        executables: mut paths,
+        extras,
+    } = fetch_and_extract(
+        &dirs,
+        &tag_name,
+        assets,
+        binary.options.asset.as_deref(),
+        target,
+        &extract::Select::new(&binary.options.bin, &[main_name, &binary.repo.name]),
+        progress,
+    )
+    .await
+    .with_context(|| "failed to extract".to_string())?;
+
+    // Keep the installed name, which may have been changed with `binge rename`.
+    rename_main(&mut paths, main_name)?;
+
+    let stale_paths = binary.paths.iter().filter(|path| !paths.contains(path));
+    let stale_extras = binary.extras.iter().filter(|path| !extras.contains(path));
+
+    for stale in stale_paths.chain(stale_extras) {
+        if let Err(err) = std::fs::remove_file(stale) {
+            eprintln!("failed to remove {stale:?}: {err}");
+        }
     }
 
     Ok(Binary {
-        repo: binary.repo.clone(),
-        path: binary.path.clone(),
         version: tag_name,
+        paths,
+        extras,
+        ..binary.clone()
     })
 }
 
@@ -376,6 +1267,204 @@ mod tests {
         (name.into(), url)
     }
 
+    fn make_asset(name: &str) -> Asset {
+        Asset {
+            name: name.into(),
+            url: format!("https://foo.com/{name}"),
+            size: 0,
+        }
+    }
+
+    fn make_release(tag_name: &str) -> Release {
+        Release {
+            tag_name: tag_name.into(),
+            prerelease: crate::version::parse(tag_name).is_some_and(|v| !v.pre.is_empty()),
+            draft: false,
+            published_at: None,
+            body: None,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn newest_release() {
+        let releases = ["v1.1.0", "v1.2.0-rc.1", "v1.0.0"].map(make_release);
+        assert_eq!(newest(releases).unwrap().tag_name, "v1.2.0-rc.1");
+
+        let releases = ["nightly", "v1.2.0"].map(make_release);
+        assert_eq!(newest(releases).unwrap().tag_name, "nightly");
+
+        assert!(newest(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn select_channel() -> Result<()> {
+        let releases = || ["v1.2.0-rc.1", "v1.1.0", "v1.0.0"].map(make_release).into();
+
+        let stable = Options::default();
+        let selection = select(releases(), &stable, None, None)?;
+        assert_eq!(selection.release.tag_name, "v1.1.0");
+
+        let prerelease = Options {
+            channel: Channel::Prerelease,
+            ..Options::default()
+        };
+        let selection = select(releases(), &prerelease, None, None)?;
+        assert_eq!(selection.release.tag_name, "v1.2.0-rc.1");
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_constraint() -> Result<()> {
+        let releases = || {
+            ["v2.1.0", "v2.0.0", "v1.5.1", "v1.4.0"]
+                .map(make_release)
+                .into()
+        };
+
+        let constraint = VersionReq::parse("^1.4")?;
+        let selection = select(releases(), &Options::default(), Some(&constraint), None)?;
+        assert_eq!(selection.release.tag_name, "v1.5.1");
+        assert!(matches!(
+            selection.excluded.as_slice(),
+            [Excluded {
+                release,
+                reason: Exclusion::Constraint
+            }] if release.tag_name == "v2.1.0"
+        ));
+
+        let constraint = VersionReq::parse("^2")?;
+        let selection = select(releases(), &Options::default(), Some(&constraint), None)?;
+        assert_eq!(selection.release.tag_name, "v2.1.0");
+        assert!(selection.excluded.is_empty());
+
+        let constraint = VersionReq::parse("^3")?;
+        assert!(select(releases(), &Options::default(), Some(&constraint), None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_cooldown() -> Result<()> {
+        let now: Timestamp = "2026-10-16T12:00:00Z".parse()?;
+
+        let releases = || {
+            [
+                ("v1.3.0", "2026-10-15T00:00:00Z"),
+                ("v1.2.0", "2026-10-12T00:00:00Z"),
+                ("v1.1.0", "2026-10-01T00:00:00Z"),
+            ]
+            .map(|(tag_name, published_at)| Release {
+                published_at: published_at.parse().ok(),
+                ..make_release(tag_name)
+            })
+            .into()
+        };
+
+        let cooldown = Cooldown {
+            now,
+            period: SignedDuration::from_hours(7 * 24),
+        };
+
+        let selection = select(releases(), &Options::default(), None, Some(cooldown))?;
+        assert_eq!(selection.release.tag_name, "v1.1.0");
+
+        let eligible = selection
+            .excluded
+            .iter()
+            .map(|excluded| match excluded.reason {
+                Exclusion::Cooldown(at) => (excluded.release.tag_name.as_str(), at.to_string()),
+                Exclusion::Constraint => unreachable!(),
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            eligible,
+            [
+                ("v1.3.0", "2026-10-22T00:00:00Z".to_string()),
+                ("v1.2.0", "2026-10-19T00:00:00Z".to_string())
+            ]
+        );
+
+        let cooldown = Cooldown {
+            now,
+            period: SignedDuration::from_hours(30 * 24),
+        };
+
+        assert!(select(releases(), &Options::default(), None, Some(cooldown)).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn select_tag_pattern() -> Result<()> {
+        let releases = || {
+            ["sdk-v3.0.0", "cli-v1.2.0", "sdk-v2.9.0", "cli-v1.1.0"]
+                .map(make_release)
+                .into()
+        };
+
+        let options = Options {
+            tag_pattern: Some("^cli-".into()),
+            ..Options::default()
+        };
+        let selection = select(releases(), &options, None, None)?;
+        assert_eq!(selection.release.tag_name, "cli-v1.2.0");
+
+        let options = Options {
+            tag_pattern: Some("^app-".into()),
+            ..Options::default()
+        };
+        assert!(select(releases(), &options, None, None).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn releases_between() {
+        let tags = |releases: Vec<Release>| {
+            releases
+                .into_iter()
+                .map(|release| release.tag_name)
+                .collect::<Vec<_>>()
+        };
+
+        let releases = || ["v2.1.0", "v2.0.0", "v1.5.2", "v1.5.1", "v1.5.0"].map(make_release);
+        assert_eq!(
+            tags(between(releases(), "v1.5.1", "v2.0.0")),
+            ["v2.0.0", "v1.5.2"]
+        );
+        assert!(between(releases(), "v2.1.0", "v2.1.0").is_empty());
+
+        let releases = ["tip", "nightly", "edge"].map(make_release);
+        assert_eq!(tags(between(releases, "edge", "tip")), ["tip", "nightly"]);
+    }
+
+    #[test]
+    fn release_status() {
+        assert!(matches!(
+            status("v1.0.0", make_release("v1.0.0")),
+            Status::Current
+        ));
+        assert!(matches!(
+            status("1.0.0", make_release("v1.0")),
+            Status::Current
+        ));
+        assert!(matches!(
+            status("v1.0.0", make_release("v1.1.0")),
+            Status::Outdated(_)
+        ));
+        assert!(matches!(
+            status("v1.1.0", make_release("v1.0.0")),
+            Status::Ahead(_)
+        ));
+        assert!(matches!(
+            status("nightly-1", make_release("nightly-2")),
+            Status::Outdated(_)
+        ));
+    }
+
     #[test]
     fn parse_arch_os() -> Result<()> {
         let (name, url) = make_filename_and_url("bar-x86_64-unknown-linux-gnu.tar.gz");
@@ -391,6 +1480,313 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn parse_arch_aliases() {
+        let matches = |name: &str, arch: &'static str| {
+            let (name, url) = make_filename_and_url(name);
+            parse_file(name, url, arch, "linux").is_some()
+        };
+
+        assert!(matches("bar-linux-amd64.tar.gz", "x86_64"));
+        assert!(matches("bar-x64-linux.tar.gz", "x86_64"));
+        assert!(matches("bar-linux-386.tar.gz", "x86"));
+        assert!(matches("bar-i686-unknown-linux-musl.tar.gz", "x86"));
+        assert!(matches("bar-linux-arm64.tar.gz", "aarch64"));
+        assert!(matches("bar-aarch64-unknown-linux-gnu.tar.gz", "aarch64"));
+        assert!(matches("bar-linux-armv7l.tar.gz", "arm"));
+        assert!(matches("bar-linux-armhf.tar.gz", "arm"));
+        assert!(matches("bar-armv7-unknown-linux-gnueabihf.tar.gz", "arm"));
+        assert!(matches("bar-riscv64gc-unknown-linux-gnu.tar.gz", "riscv64"));
+        assert!(matches("bar-linux-loong64.tar.gz", "loongarch64"));
+        assert!(matches("bar-linux-s390x", "s390x"));
+
+        assert!(!matches("bar-linux-arm64.tar.gz", "arm"));
+        assert!(!matches("bar-linux-x86_64.tar.gz", "x86"));
+        assert!(!matches("bar-linux-amd64.tar.gz", "x86"));
+        assert!(!matches("bar-linux-armv7l.tar.gz", "aarch64"));
+        assert!(!matches("bar-linux-riscv64.tar.gz", "x86_64"));
+    }
+
+    #[test]
+    fn classify_asset() {
+        let file = classify(
+            &make_asset("bar-x86_64-linux.tar.gz"),
+            "x86_64",
+            "linux",
+            None,
+        )
+        .unwrap();
+        assert_eq!(file.kind.to_string(), "tar+gz");
+
+        assert!(matches!(
+            classify(
+                &make_asset("bar-aarch64-linux.tar.gz"),
+                "x86_64",
+                "linux",
+                None
+            ),
+            Err(Rejection::ArchOs)
+        ));
+
+        assert!(matches!(
+            classify(
+                &make_asset("bar-x86_64-linux.tar.gz.sha256"),
+                "x86_64",
+                "linux",
+                None
+            ),
+            Err(Rejection::Auxiliary)
+        ));
+    }
+
+    #[test]
+    fn classify_asset_pattern() {
+        let pattern = Regex::new("-full").unwrap();
+
+        let file = classify(
+            &make_asset("bar-full-x86_64-linux.tar.gz"),
+            "x86_64",
+            "linux",
+            Some(&pattern),
+        )
+        .unwrap();
+        assert!(file.arch_os);
+
+        assert!(matches!(
+            classify(
+                &make_asset("bar-lite-x86_64-linux.tar.gz"),
+                "x86_64",
+                "linux",
+                Some(&pattern)
+            ),
+            Err(Rejection::Pattern)
+        ));
+
+        let file = classify(
+            &make_asset("bar-full.tar.gz"),
+            "x86_64",
+            "linux",
+            Some(&pattern),
+        )
+        .unwrap();
+        assert!(!file.arch_os);
+        assert_eq!(file.kind.to_string(), "tar+gz");
+    }
+
+    #[test]
+    fn explain_assets() -> Result<()> {
+        let mut release = make_release("v1.0.0");
+        release.assets = [
+            "bar-x86_64-unknown-linux-gnu.tar.gz",
+            "bar-x86_64-unknown-linux-gnu-debug.tar.gz",
+            "bar-x86_64-unknown-linux-gnu.tar.gz.sha256",
+            "bar-aarch64-unknown-linux-gnu.tar.gz",
+        ]
+        .into_iter()
+        .map(make_asset)
+        .collect();
+
+        let target = "x86_64-linux-gnu".parse()?;
+        let explanation = explain_release(release, None, &target)?;
+        let verdicts = &explanation.verdicts;
+
+        assert_eq!(explanation.tag, "v1.0.0");
+        assert!(verdicts[0].chosen);
+        assert!(verdicts[0].arch_os);
+        assert_eq!(verdicts[0].kind.to_string(), "tar+gz");
+        assert!(verdicts[0].outcome.as_ref().unwrap() > verdicts[1].outcome.as_ref().unwrap());
+        assert!(!verdicts[1].chosen);
+        assert!(matches!(verdicts[2].outcome, Err(Rejection::Auxiliary)));
+        assert!(!verdicts[3].arch_os);
+        assert!(matches!(verdicts[3].outcome, Err(Rejection::ArchOs)));
+
+        let mut release = make_release("v1.0.0");
+        release.assets = ["bar_1.0.0_arm64.deb", "bar_1.0.0_amd64.deb"]
+            .into_iter()
+            .map(make_asset)
+            .collect();
+
+        let explanation = explain_release(release, None, &target)?;
+        let verdicts = &explanation.verdicts;
+
+        assert!(!verdicts[0].arch_os);
+        assert!(matches!(verdicts[0].outcome, Err(Rejection::ArchOs)));
+        assert!(verdicts[1].arch_os);
+        assert!(verdicts[1].chosen);
+        assert_eq!(verdicts[1].kind.to_string(), "deb");
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn refuse_rename_onto_taken_path() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let dirs = extract::Dirs {
+            bin: dir.path().to_path_buf(),
+            data: None,
+            taken: vec![dir.path().join("tool")],
+        };
+
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let result = install(
+            reqwest::Client::new(),
+            "bar/b:tool".parse()?,
+            Options::default(),
+            0,
+            &"x86_64-linux".parse()?,
+            &dirs,
+            tx,
+        )
+        .await;
+
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .starts_with("refusing to overwrite")
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn offer_installable_assets() -> Result<()> {
+        let assets = ["bar-1.0.0.tar.gz", "bar-1.0.0.tar.gz.sha256"].map(make_asset);
+
+        let target = "x86_64-linux-gnu".parse()?;
+        let (candidates, rejected) = choose(&assets, None, &target);
+        assert!(candidates.is_empty());
+
+        let offers = offers(&assets, &rejected, "v1.0.0", &target);
+        assert_eq!(offers.len(), 1);
+        assert_eq!(offers[0].name, "bar-1.0.0.tar.gz");
+        assert_eq!(offers[0].kind.to_string(), "tar+gz");
+        assert!(!offers[0].arch_os);
+        assert!(matches!(offers[0].rejection, Rejection::ArchOs));
+        assert!(Regex::new(&offers[0].pattern)?.is_match("bar-1.1.0.tar.gz"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn derive_asset_pattern() {
+        let pattern = asset_pattern("hugo_extended_0.120.0_Linux-64bit.tar.gz", "v0.120.0");
+        assert_eq!(pattern, r"^hugo_extended_.+_Linux\-64bit\.tar\.gz$");
+
+        let pattern = Regex::new(&pattern).unwrap();
+        assert!(pattern.is_match("hugo_extended_0.121.1_Linux-64bit.tar.gz"));
+        assert!(!pattern.is_match("hugo_0.121.1_Linux-64bit.tar.gz"));
+
+        assert_eq!(
+            asset_pattern("bar-v1.0-linux.tar.gz", "v1.0"),
+            r"^bar\-.+\-linux\.tar\.gz$"
+        );
+        assert_eq!(asset_pattern("bar.AppImage", "v1.0"), r"^bar\.AppImage$");
+    }
+
+    fn ranked(names: &[&str], libc: Option<Libc>) -> Vec<String> {
+        ranked_on(names, "x86_64", libc)
+    }
+
+    fn ranked_on(names: &[&str], arch: &str, libc: Option<Libc>) -> Vec<String> {
+        let mut candidates = names
+            .iter()
+            .filter_map(|name| {
+                let (name, url) = make_filename_and_url(name);
+                parse_file(name, url, arch, "linux")
+            })
+            .collect::<Vec<_>>();
+
+        rank(&mut candidates, arch, libc);
+
+        candidates
+            .into_iter()
+            .map(|file| file.filename.to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn rank_libc() {
+        let names = [
+            "bar-x86_64-unknown-linux-gnu.tar.gz",
+            "bar-x86_64-unknown-linux-musl.tar.gz",
+        ];
+
+        assert_eq!(ranked(&names, Some(Libc::Gnu))[0], names[0]);
+        assert_eq!(ranked(&names, Some(Libc::Musl))[0], names[1]);
+
+        let names = [
+            "bar-linux-amd64-glibc.tar.gz",
+            "bar-linux-amd64-static.tar.gz",
+        ];
+        assert_eq!(ranked(&names, Some(Libc::Musl))[0], names[1]);
+        assert_eq!(ranked(&names, Some(Libc::Gnu))[0], names[0]);
+        assert_eq!(ranked(&names, None)[0], names[1]);
+
+        let names = [
+            "bar-x86_64-unknown-linux-gnu.tar.gz",
+            "bar-x86_64-unknown-linux-musl.tar.gz",
+            "bar-x86_64-linux-static.tar.gz",
+        ];
+        assert_eq!(ranked(&names, Some(Libc::Musl))[1], names[2]);
+        assert_eq!(ranked(&names, Some(Libc::Gnu))[1], names[2]);
+    }
+
+    #[test]
+    fn rank_penalties() {
+        let names = [
+            "bar-debug-x86_64-unknown-linux-gnu.tar.gz",
+            "bar-x86_64-unknown-linux-gnu-src.tar.gz",
+            "bar-x86_64-unknown-linux-gnu-symbols.tar.gz",
+            "bar-x86_64-unknown-linux-gnu.zip.xz",
+            "bar-x86_64-unknown-linux-gnu.tar.gz",
+        ];
+
+        assert_eq!(ranked(&names, Some(Libc::Gnu))[0], names[4]);
+
+        let names = ["bar_1.0.0_amd64.deb", "bar-linux-amd64.tar.gz"];
+        assert_eq!(ranked(&names, Some(Libc::Gnu))[0], names[1]);
+    }
+
+    #[test]
+    fn rank_hard_float() {
+        let names = [
+            "bar-linux-armel.tar.gz",
+            "bar-linux-armv6.tar.gz",
+            "bar-linux-armhf.tar.gz",
+        ];
+        assert_eq!(ranked_on(&names, "arm", Some(Libc::Gnu))[0], names[2]);
+
+        let names = [
+            "bar-arm-unknown-linux-gnueabi.tar.gz",
+            "bar-armv7-unknown-linux-gnueabihf.tar.gz",
+        ];
+        assert_eq!(ranked_on(&names, "arm", Some(Libc::Gnu))[0], names[1]);
+    }
+
+    #[test]
+    fn rank_deterministic() {
+        let names = [
+            "bar-linux-x64.tar.gz",
+            "bar-x86_64-linux.tar.gz",
+            "bar-linux-amd64.tar.gz",
+            "bar-amd64-linux.tar.gz",
+        ];
+
+        let mut reversed = names;
+        reversed.reverse();
+
+        assert_eq!(
+            ranked(&names, Some(Libc::Gnu)),
+            ranked(&reversed, Some(Libc::Gnu))
+        );
+        assert_eq!(
+            ranked(&names, Some(Libc::Gnu))[0],
+            "bar-x86_64-linux.tar.gz"
+        );
+        assert_eq!(ranked(&names, Some(Libc::Gnu))[1], "bar-linux-x64.tar.gz");
+    }
+
     #[test]
     fn parse_compression() -> Result<()> {
         let (name, url) = make_filename_and_url("bar-x86_64-unknown-linux-gnu.tar.gz");
@@ -425,6 +1821,26 @@ mod tests {
         let file = parse_file(name, url, "x86_64", "linux").unwrap();
         assert!(matches!(file.kind, Compression::Zstd(Archive::None)));
 
+        for (name, kind) in [
+            ("bar-x86_64-unknown-linux-gnu.tgz", "tar+gz"),
+            ("bar-x86_64-unknown-linux-gnu.txz", "tar+xz"),
+            ("bar-x86_64-unknown-linux-gnu.tzst", "tar+zst"),
+            ("bar-x86_64-unknown-linux-gnu.tbz", "tar+bz2"),
+            ("bar-x86_64-unknown-linux-gnu.tbz2", "tar+bz2"),
+            ("bar-x86_64-unknown-linux-gnu.tar.bz2", "tar+bz2"),
+            ("bar-x86_64-unknown-linux-gnu.tar.lz4", "tar+lz4"),
+            ("bar-x86_64-unknown-linux-gnu.bz2", "file+bz2"),
+            ("bar-x86_64-unknown-linux-gnu.lz4", "file+lz4"),
+            ("bar-x86_64-unknown-linux-gnu.zip.gz", "zip+gz"),
+        ] {
+            let (name, url) = make_filename_and_url(name);
+            let file = parse_file(name, url, "x86_64", "linux").unwrap();
+            assert_eq!(file.kind.to_string(), kind);
+            assert!(file.kind.is_supported());
+        }
+
+        assert!(!Compression::Gz(Archive::Deb).is_supported());
+
         let (name, url) = make_filename_and_url("bar-x86_64-unknown-linux-gnu.zip");
         let file = parse_file(name, url, "x86_64", "linux").unwrap();
         assert!(matches!(file.kind, Compression::None(Archive::Zip)));
@@ -437,6 +1853,29 @@ mod tests {
         let file = parse_file(name, url, "x86_64", "linux").unwrap();
         assert!(matches!(file.kind, Compression::None(Archive::None)));
 
+        let (name, url) = make_filename_and_url("bar_1.2.3_amd64.deb");
+        let file = parse_file(name.clone(), url.clone(), "x86_64", "linux").unwrap();
+        assert!(matches!(file.kind, Compression::None(Archive::Deb)));
+        assert!(parse_file(name, url, "x86_64", "macos").is_none());
+
+        let (name, url) = make_filename_and_url("bar-1.2.3-1.x86_64.rpm");
+        let file = parse_file(name.clone(), url.clone(), "x86_64", "linux").unwrap();
+        assert!(matches!(file.kind, Compression::None(Archive::Rpm)));
+        assert!(parse_file(name, url, "x86", "linux").is_none());
+
+        let (name, url) = make_filename_and_url("Bar-1.2.3-x86_64.AppImage");
+        let file = parse_file(name.clone(), url.clone(), "x86_64", "linux").unwrap();
+        assert!(matches!(file.kind, Compression::None(Archive::AppImage)));
+        assert!(parse_file(name, url, "aarch64", "linux").is_none());
+
+        let (name, url) = make_filename_and_url("bar_arm64.AppImage");
+        assert!(parse_file(name.clone(), url.clone(), "aarch64", "linux").is_some());
+        assert!(parse_file(name, url, "x86_64", "linux").is_none());
+
+        let (name, url) = make_filename_and_url("Bar-1.2.3.AppImage");
+        assert!(parse_file(name.clone(), url.clone(), "x86_64", "linux").is_some());
+        assert!(parse_file(name, url, "aarch64", "linux").is_none());
+
         Ok(())
     }
 }
@@ -0,0 +1,344 @@
+//! Properties of the host system relevant for choosing assets.
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::anyhow;
+use serde::{Deserialize, Serialize};
+
+/// C standard library binaries are linked against.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Eq, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Libc {
+    /// GNU C library.
+    Gnu,
+    /// musl libc.
+    Musl,
+}
+
+impl std::fmt::Display for Libc {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Libc::Gnu => write!(f, "gnu"),
+            Libc::Musl => write!(f, "musl"),
+        }
+    }
+}
+
+impl FromStr for Libc {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "gnu" => Ok(Libc::Gnu),
+            "musl" => Ok(Libc::Musl),
+            _ => Err(anyhow!("unknown libc `{s}`, expected `gnu` or `musl`")),
+        }
+    }
+}
+
+/// Platform assets are chosen for, written as `<arch>-<os>[-<libc>]` using Rust's names for
+/// architecture and OS, e.g. `aarch64-linux-musl`.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+#[serde(into = "String", try_from = "String")]
+pub(crate) struct Target {
+    pub arch: String,
+    pub os: String,
+    pub libc: Option<Libc>,
+}
+
+impl Target {
+    /// Return the target of this host, with a detected or configured `libc`.
+    pub(crate) fn host(libc: Option<Libc>) -> Self {
+        Self {
+            arch: std::env::consts::ARCH.to_string(),
+            os: std::env::consts::OS.to_string(),
+            libc,
+        }
+    }
+}
+
+impl FromStr for Target {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        let mut split = s.split('-');
+
+        let (Some(arch), Some(os)) = (split.next(), split.next()) else {
+            return Err(anyhow!(
+                "target `{s}` is not of the form <arch>-<os>[-<libc>]"
+            ));
+        };
+
+        if arch.is_empty() || os.is_empty() {
+            return Err(anyhow!(
+                "target `{s}` is not of the form <arch>-<os>[-<libc>]"
+            ));
+        }
+
+        let libc = split.next().map(Libc::from_str).transpose()?;
+
+        if split.next().is_some() {
+            return Err(anyhow!(
+                "target `{s}` is not of the form <arch>-<os>[-<libc>]"
+            ));
+        }
+
+        Ok(Self {
+            arch: arch.to_string(),
+            os: os.to_string(),
+            libc,
+        })
+    }
+}
+
+impl TryFrom<String> for Target {
+    type Error = anyhow::Error;
+
+    fn try_from(value: String) -> std::result::Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Target> for String {
+    fn from(target: Target) -> Self {
+        target.to_string()
+    }
+}
+
+impl std::fmt::Display for Target {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.arch, self.os)?;
+
+        if let Some(libc) = self.libc {
+            write!(f, "-{libc}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Detect the host libc from the dynamic loader system executables are linked against, falling
+/// back to the loaders installed in `/lib` and `/lib64`.
+pub(crate) fn detect_libc() -> Option<Libc> {
+    ["/bin/sh", "/usr/bin/env"]
+        .into_iter()
+        .find_map(|path| {
+            let elf = std::fs::read(path).ok()?;
+            libc_of_loader(interpreter(&elf)?)
+        })
+        .or_else(|| detect_libc_in(&[Path::new("/lib"), Path::new("/lib64")]))
+}
+
+/// Return the libc the dynamic loader at `path` belongs to.
+fn libc_of_loader(path: &str) -> Option<Libc> {
+    let name = Path::new(path).file_name()?.to_str()?;
+
+    if name.starts_with("ld-musl-") {
+        Some(Libc::Musl)
+    } else if name.starts_with("ld-linux") {
+        Some(Libc::Gnu)
+    } else {
+        None
+    }
+}
+
+/// Return the program interpreter, i.e. the dynamic loader, requested by the `PT_INTERP` program
+/// header of the ELF image `elf`.
+fn interpreter(elf: &[u8]) -> Option<&str> {
+    const PT_INTERP: u64 = 3;
+
+    if !elf.starts_with(b"\x7fELF") {
+        return None;
+    }
+
+    let wide = match elf.get(4)? {
+        1 => false,
+        2 => true,
+        _ => return None,
+    };
+
+    let little = match elf.get(5)? {
+        1 => true,
+        2 => false,
+        _ => return None,
+    };
+
+    let int = |offset: u64, size: u64| -> Option<u64> {
+        let start = usize::try_from(offset).ok()?;
+        let end = start.checked_add(usize::try_from(size).ok()?)?;
+        let bytes = elf.get(start..end)?;
+        let fold = |value: u64, byte: &u8| (value << 8) | u64::from(*byte);
+
+        Some(if little {
+            bytes.iter().rev().fold(0, fold)
+        } else {
+            bytes.iter().fold(0, fold)
+        })
+    };
+
+    let (offset, entry_size, count) = if wide {
+        (int(0x20, 8)?, int(0x36, 2)?, int(0x38, 2)?)
+    } else {
+        (int(0x1c, 4)?, int(0x2a, 2)?, int(0x2c, 2)?)
+    };
+
+    for index in 0..count {
+        let header = offset.checked_add(index.checked_mul(entry_size)?)?;
+
+        if int(header, 4)? != PT_INTERP {
+            continue;
+        }
+
+        let (start, size) = if wide {
+            (int(header + 8, 8)?, int(header + 32, 8)?)
+        } else {
+            (int(header + 4, 4)?, int(header + 16, 4)?)
+        };
+
+        let start = usize::try_from(start).ok()?;
+        let end = start.checked_add(usize::try_from(size).ok()?)?;
+        let path = std::str::from_utf8(elf.get(start..end)?).ok()?;
+
+        return Some(path.trim_end_matches('\0'));
+    }
+
+    None
+}
+
+/// Detect the libc from the dynamic loaders found in `dirs`. Because glibc hosts may have the musl
+/// loader installed by a `musl` package and musl hosts a glibc compatibility layer, finding both
+/// is inconclusive.
+fn detect_libc_in(dirs: &[&Path]) -> Option<Libc> {
+    let loaders = dirs
+        .iter()
+        .filter_map(|dir| std::fs::read_dir(dir).ok())
+        .flatten()
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .collect::<Vec<_>>();
+
+    let musl = loaders.iter().any(|name| name.starts_with("ld-musl-"));
+    let gnu = loaders.iter().any(|name| name.starts_with("ld-linux"));
+
+    match (musl, gnu) {
+        (true, false) => Some(Libc::Musl),
+        (false, true) => Some(Libc::Gnu),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detect_loader() -> std::io::Result<()> {
+        let temp = tempfile::tempdir()?;
+        let dir = temp.path();
+        let musl = dir.join("musl");
+        let gnu = dir.join("gnu");
+
+        std::fs::create_dir_all(&musl)?;
+        std::fs::create_dir_all(&gnu)?;
+        std::fs::write(musl.join("ld-musl-x86_64.so.1"), b"")?;
+        std::fs::write(gnu.join("ld-linux-x86-64.so.2"), b"")?;
+
+        assert_eq!(detect_libc_in(&[&musl]), Some(Libc::Musl));
+        assert_eq!(detect_libc_in(&[&gnu]), Some(Libc::Gnu));
+        // Debian's `musl` package installs its loader on glibc hosts.
+        assert_eq!(detect_libc_in(&[&gnu, &musl]), None);
+        assert_eq!(detect_libc_in(&[dir]), None);
+
+        Ok(())
+    }
+
+    /// Build a minimal ELF image with a `PT_INTERP` program header requesting `loader`.
+    fn make_elf(wide: bool, little: bool, loader: &str) -> Vec<u8> {
+        let int = |value: u64, size: usize| {
+            let bytes = value.to_le_bytes();
+            let mut bytes = bytes[..size].to_vec();
+
+            if !little {
+                bytes.reverse();
+            }
+
+            bytes
+        };
+
+        let (header_size, entry_size) = if wide { (64, 56) } else { (52, 32) };
+        let interp = header_size + entry_size;
+
+        let mut elf = vec![0x7f, b'E', b'L', b'F', if wide { 2 } else { 1 }];
+        elf.push(if little { 1 } else { 2 });
+        elf.resize(header_size, 0);
+
+        let mut put = |offset: usize, value: u64, size: usize| {
+            elf[offset..offset + size].copy_from_slice(&int(value, size));
+        };
+
+        if wide {
+            put(0x20, header_size as u64, 8);
+            put(0x36, entry_size as u64, 2);
+            put(0x38, 1, 2);
+        } else {
+            put(0x1c, header_size as u64, 4);
+            put(0x2a, entry_size as u64, 2);
+            put(0x2c, 1, 2);
+        }
+
+        let mut entry = vec![0; entry_size];
+        let mut put = |offset: usize, value: u64, size: usize| {
+            entry[offset..offset + size].copy_from_slice(&int(value, size));
+        };
+
+        put(0, 3, 4);
+
+        if wide {
+            put(8, interp as u64, 8);
+            put(32, loader.len() as u64 + 1, 8);
+        } else {
+            put(4, interp as u64, 4);
+            put(16, loader.len() as u64 + 1, 4);
+        }
+
+        elf.extend(entry);
+        elf.extend(loader.bytes());
+        elf.push(0);
+        elf
+    }
+
+    #[test]
+    fn read_interpreter() {
+        let elf = make_elf(true, true, "/lib64/ld-linux-x86-64.so.2");
+        assert_eq!(interpreter(&elf), Some("/lib64/ld-linux-x86-64.so.2"));
+        assert_eq!(libc_of_loader(interpreter(&elf).unwrap()), Some(Libc::Gnu));
+
+        let elf = make_elf(false, false, "/lib/ld-musl-mips.so.1");
+        assert_eq!(interpreter(&elf), Some("/lib/ld-musl-mips.so.1"));
+        assert_eq!(libc_of_loader(interpreter(&elf).unwrap()), Some(Libc::Musl));
+
+        assert_eq!(interpreter(&elf[..40]), None);
+        assert_eq!(interpreter(b"#!/bin/sh"), None);
+    }
+
+    #[test]
+    fn parse_target() -> anyhow::Result<()> {
+        let target: Target = "aarch64-linux-musl".parse()?;
+        assert_eq!(target.arch, "aarch64");
+        assert_eq!(target.os, "linux");
+        assert_eq!(target.libc, Some(Libc::Musl));
+        assert_eq!(target.to_string(), "aarch64-linux-musl");
+
+        let target: Target = "x86_64-linux".parse()?;
+        assert_eq!(target.arch, "x86_64");
+        assert_eq!(target.libc, None);
+        assert_eq!(target.to_string(), "x86_64-linux");
+
+        assert!("x86_64".parse::<Target>().is_err());
+        assert!("x86_64-".parse::<Target>().is_err());
+        assert!("arm-linux-uclibc".parse::<Target>().is_err());
+        assert!("arm-unknown-linux-gnu".parse::<Target>().is_err());
+
+        Ok(())
+    }
+}
@@ -1,13 +1,20 @@
 mod config;
 mod extract;
 mod gh;
+mod host;
 mod manifest;
+mod markdown;
+mod version;
 
-use anyhow::Result;
+use std::io::{IsTerminal, Write};
+use std::os::unix::fs::PermissionsExt;
+use std::path::PathBuf;
+
+use anyhow::{Result, anyhow};
 use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
 use clap_complete::{Shell, generate};
 use futures_lite::StreamExt;
-use gh::Release;
+use gh::{Excluded, Exclusion, Outcome, Release, Status};
 use owo_colors::OwoColorize;
 use strides::future::{FutureExt as _, join};
 use strides::{Gradient, Layout, Rgb, Segment};
@@ -15,7 +22,7 @@ use tokio::sync::mpsc::unbounded_channel;
 use tokio_stream::wrappers::UnboundedReceiverStream;
 
 use manifest::Repo;
-use manifest::{Binary, Manifest};
+use manifest::{Binary, Manifest, Options, Retained};
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -30,15 +37,49 @@ enum Commands {
     /// Generate shell completion.
     Completion { shell: Shell },
     /// Install release binaries from the given repos.
-    Install { repos: Vec<Repo> },
+    Install {
+        repos: Vec<Repo>,
+        #[command(flatten)]
+        options: Options,
+        /// Install into this directory instead of the configured one.
+        #[arg(long, value_name = "DIR")]
+        install_path: Option<PathBuf>,
+        /// Explain which release asset is chosen and why others are not.
+        #[arg(long)]
+        explain: bool,
+    },
     /// Uninstall release binaries.
     Uninstall { repos: Vec<Repo> },
     /// Find and install updates for installed binaries.
-    Update,
+    Update {
+        /// Explain which release asset is chosen and why others are not.
+        #[arg(long)]
+        explain: bool,
+    },
     /// Check for updates but do not install them.
-    Check,
+    Check {
+        /// Show release notes of all releases newer than the installed ones.
+        #[arg(long)]
+        notes: bool,
+        /// Explain which release asset is chosen and why others are not.
+        #[arg(long)]
+        explain: bool,
+    },
     /// Rename a binary.
     Rename { repo: Repo },
+    /// Restore the previously installed version of a binary.
+    Rollback { repo: Repo },
+    /// Hold binaries at their installed version during update.
+    Pin { repos: Vec<Repo> },
+    /// Release held binaries so they are updated again.
+    Unpin { repos: Vec<Repo> },
+    /// List recent releases of a repo or the assets of `owner/repo@tag`.
+    Releases {
+        repo: Repo,
+        /// Number of releases to list.
+        #[arg(long, default_value_t = 10)]
+        count: usize,
+    },
     /// List installed binaries
     List {
         /// Dump the list in a format that can be used in the install command.
@@ -112,6 +153,9 @@ fn progress_theme() -> strides::Theme {
 /// Install all `repos` and update the `manifest`.
 async fn install(
     repos: Vec<Repo>,
+    options: &Options,
+    install_path: Option<PathBuf>,
+    explain: bool,
     config: &config::Config,
     mut manifest: Manifest,
     token: Option<String>,
@@ -128,50 +172,151 @@ async fn install(
         println!("{} already installed", already_installed.join(", "));
     }
 
-    let label_width = max_label_width("installing", to_be_installed.iter());
-    let mut group = strides::future::Group::new(progress_theme())
-        .with_spinner_style(SPINNER_STYLE)
-        .with_elapsed_time();
-
     let client = gh::make_client(token)?;
-    let install_path = config.install_path()?;
+    // Completions and man pages are only of use for binaries installed for this host.
+    let data = (install_path.is_none() && options.target.is_none())
+        .then(|| config.data_home())
+        .flatten();
+    let install_path = install_path.map_or_else(|| config.install_path(), Ok)?;
+    let target = options.target.clone().unwrap_or_else(|| config.host());
+    let interactive = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+
+    if explain {
+        explain_assets(
+            &client,
+            to_be_installed.iter().map(|repo| (repo, options)),
+            config,
+        )
+        .await;
+    }
 
-    for repo in to_be_installed {
-        let message = aligned_label("installing", &repo, label_width);
-        let (tx, rx) = unbounded_channel::<f64>();
+    let mut pending = to_be_installed
+        .into_iter()
+        .map(|repo| (repo, options.clone()))
+        .collect::<Vec<_>>();
 
-        group.push(
-            {
-                let client = client.clone();
-                let install_path = install_path.clone();
-                async move { gh::install(client, repo, &install_path, tx).await }
+    while !pending.is_empty() {
+        let label_width = max_label_width("installing", pending.iter().map(|(repo, _)| repo));
+        let mut group = strides::future::Group::new(progress_theme())
+            .with_spinner_style(SPINNER_STYLE)
+            .with_elapsed_time();
+
+        for (repo, options) in pending.drain(..) {
+            let message = aligned_label("installing", &repo, label_width);
+            let (tx, rx) = unbounded_channel::<f64>();
+
+            group.push(
+                {
+                    let client = client.clone();
+                    let dirs = extract::Dirs {
+                        bin: install_path.clone(),
+                        data: data.clone(),
+                        taken: manifest
+                            .binaries
+                            .iter()
+                            .filter(|binary| binary.repo != repo)
+                            .flat_map(Binary::installed)
+                            .cloned()
+                            .collect(),
+                    };
+                    let target = target.clone();
+                    let cooldown = options.cooldown.unwrap_or(config.cooldown());
+                    async move {
+                        let result = gh::install(
+                            client,
+                            repo.clone(),
+                            options.clone(),
+                            cooldown,
+                            &target,
+                            &dirs,
+                            tx,
+                        )
+                        .await;
+
+                        (repo, options, result)
+                    }
+                }
+                .with_label(message)
+                .with_progress(UnboundedReceiverStream::new(rx)),
+            );
+        }
+
+        let results = group.collect::<Vec<_>>().await;
+
+        for (repo, options, result) in results {
+            match result {
+                Ok(binary) => {
+                    println!(
+                        "{} {} {}",
+                        "Installed ".bright_green().bold(),
+                        binary.repo,
+                        binary.version
+                    );
+
+                    manifest.update(binary);
+                }
+                Err(err) => match err.downcast_ref::<gh::NoAsset>() {
+                    Some(no_asset) if interactive && !no_asset.offers.is_empty() => {
+                        if let Some(asset) = pick_asset(&repo, no_asset)? {
+                            let options = Options {
+                                asset: Some(asset),
+                                ..options
+                            };
+
+                            pending.push((repo, options));
+                        }
+                    }
+                    _ => eprintln!("{}: {err}", "Error".bright_red().bold()),
+                },
             }
-            .with_label(message)
-            .with_progress(UnboundedReceiverStream::new(rx)),
+        }
+    }
+
+    Ok(manifest)
+}
+
+/// Let the user pick one of the assets that did not match for `repo` and return a pattern matching
+/// it in later releases. Returns `None` if the user skips the binary.
+fn pick_asset(repo: &Repo, no_asset: &gh::NoAsset) -> Result<Option<String>> {
+    println!(
+        "No asset of {repo} {} matches {}, pick one:",
+        no_asset.tag, no_asset.target
+    );
+
+    for (index, offer) in no_asset.offers.iter().enumerate() {
+        let arch_os = if offer.arch_os {
+            "arch/OS match"
+        } else {
+            "no arch/OS match"
+        };
+
+        println!(
+            "{:>4} {} {}",
+            index + 1,
+            offer.name,
+            format!("({}, {arch_os}, {})", offer.kind, offer.rejection).bright_black()
         );
     }
 
-    let results = group.collect::<Vec<_>>().await;
+    let range = 1..=no_asset.offers.len();
 
-    for result in results {
-        match result {
-            Ok(binary) => {
-                println!(
-                    "{} {} {}",
-                    "Installed ".bright_green().bold(),
-                    binary.repo,
-                    binary.version
-                );
+    loop {
+        print!("Asset [{}-{}, empty to skip]: ", range.start(), range.end());
+        std::io::stdout().flush()?;
 
-                manifest.update(binary);
-            }
-            Err(err) => {
-                eprintln!("{}: {err}", "Error".bright_red().bold());
+        let mut line = String::new();
+
+        if std::io::stdin().read_line(&mut line)? == 0 || line.trim().is_empty() {
+            return Ok(None);
+        }
+
+        match line.trim().parse::<usize>() {
+            Ok(choice) if range.contains(&choice) => {
+                return Ok(Some(no_asset.offers[choice - 1].pattern.clone()));
             }
+            _ => println!("{} is not a valid choice", line.trim()),
         }
     }
-
-    Ok(manifest)
 }
 
 /// Uninstall all `repos` and update the provided manifest.
@@ -181,8 +326,10 @@ fn uninstall(repos: Vec<Repo>, Manifest { version, binaries }: Manifest) -> Resu
         .partition(|binary| repos.contains(&binary.repo));
 
     for binary in to_be_uninstalled {
-        if let Err(err) = std::fs::remove_file(&binary.path) {
-            eprintln!("failed to remove {:?}: {err}", binary.path);
+        binary.remove();
+
+        for retained in &binary.retained {
+            retained.remove();
         }
 
         println!("{} {}", "Uninstalled".bright_green().bold(), binary.repo);
@@ -191,31 +338,183 @@ fn uninstall(repos: Vec<Repo>, Manifest { version, binaries }: Manifest) -> Resu
     Ok(Manifest { version, binaries })
 }
 
+/// Keep a copy of the installed executables of `binary` if rollbacks are enabled.
+fn retain(binary: &Binary, config: &config::Config) -> Result<Option<Retained>> {
+    if config.retained_versions() == 0 {
+        return Ok(None);
+    }
+
+    let dir = config.retained_path(&binary.repo, &binary.version)?;
+    Ok(Some(binary.retain(&dir)?))
+}
+
+/// Print for each repo with its options which asset of the release would be installed and how all
+/// assets were judged.
+async fn explain_assets<'a>(
+    client: &reqwest::Client,
+    repos: impl IntoIterator<Item = (&'a Repo, &'a Options)>,
+    config: &config::Config,
+) {
+    let host = config.host();
+
+    for (repo, options) in repos {
+        let target = options.target.as_ref().unwrap_or(&host);
+        let cooldown = options.cooldown.unwrap_or(config.cooldown());
+
+        let explanation = match gh::explain(client.clone(), repo, options, cooldown, target).await {
+            Ok(explanation) => explanation,
+            Err(err) => {
+                eprintln!(
+                    "{}: failed to explain {repo}: {err}",
+                    "Error".bright_red().bold()
+                );
+                continue;
+            }
+        };
+
+        println!("{repo} {} for {}", explanation.tag, explanation.target);
+
+        for verdict in &explanation.verdicts {
+            print_verdict(verdict, "");
+        }
+    }
+}
+
+/// Print how an asset was judged, followed by `suffix` inside the parentheses.
+fn print_verdict(verdict: &gh::Verdict, suffix: &str) {
+    let arch_os = if verdict.arch_os {
+        "arch/OS match"
+    } else {
+        "no arch/OS match"
+    };
+
+    match &verdict.outcome {
+        Ok(score) if verdict.chosen => println!(
+            "  {} {} {}",
+            "→".bright_green(),
+            verdict.name.bold(),
+            format!(
+                "({}, {arch_os}, score {score}, chosen{suffix})",
+                verdict.kind
+            )
+            .bright_black()
+        ),
+        Ok(score) => println!(
+            "  {} {} {}",
+            "✓".bright_green(),
+            verdict.name,
+            format!("({}, {arch_os}, score {score}{suffix})", verdict.kind).bright_black()
+        ),
+        Err(rejection) => println!(
+            "  {} {} {}",
+            "✗".bright_red(),
+            verdict.name.bright_black(),
+            format!("({}, {arch_os}, {rejection}{suffix})", verdict.kind).bright_black()
+        ),
+    }
+}
+
+/// Report that a release is newer than what is installed but was not selected.
+fn print_excluded(binary: &Binary, Excluded { release, reason }: &Excluded) {
+    match reason {
+        Exclusion::Constraint => {
+            let constraint = binary
+                .repo
+                .constraint
+                .as_ref()
+                .map(ToString::to_string)
+                .unwrap_or_default();
+
+            println!(
+                "{} {} newer release {} available but excluded by constraint {constraint}",
+                "Notice".bright_yellow(),
+                binary.repo,
+                release.tag_name,
+            );
+        }
+        Exclusion::Cooldown(eligible_at) => {
+            println!(
+                "{} {} release {} in cooldown until {}",
+                "Notice".bright_yellow(),
+                binary.repo,
+                release.tag_name,
+                eligible_at.strftime("%Y-%m-%d"),
+            );
+        }
+    }
+}
+
 /// Concurrently update all installed binaries listed in the manifest.
 async fn update(
+    config: &config::Config,
     Manifest { version, binaries }: Manifest,
+    explain: bool,
     token: Option<String>,
 ) -> Result<Manifest> {
     enum Check {
-        NotFound { binary: Binary },
-        Found { binary: Binary, release: Release },
-        Error { binary: Binary, err: anyhow::Error },
+        NotFound {
+            binary: Binary,
+            excluded: Vec<Excluded>,
+        },
+        Found {
+            binary: Binary,
+            release: Release,
+            excluded: Vec<Excluded>,
+        },
+        Error {
+            binary: Binary,
+            err: anyhow::Error,
+        },
     }
 
     enum Update {
         None { binary: Binary },
-        Installed { old: Binary, new: Binary },
+        Installed { old_version: String, new: Binary },
         Error { binary: Binary, err: anyhow::Error },
     }
 
     let client = gh::make_client(token)?;
 
+    let installed = binaries
+        .iter()
+        .flat_map(|binary| {
+            binary
+                .installed()
+                .map(|path| (binary.repo.clone(), path.clone()))
+        })
+        .collect::<Vec<_>>();
+
+    let (held, binaries): (Vec<_>, Vec<_>) = binaries.into_iter().partition(|binary| binary.held);
+
+    for binary in &held {
+        println!("{} {} (held)", "Skipped".bright_black(), binary.repo);
+    }
+
+    if explain {
+        let repos = binaries
+            .iter()
+            .map(|binary| (&binary.repo, &binary.options));
+        explain_assets(&client, repos, config).await;
+    }
+
     let futs = binaries.into_iter().map(|binary| {
         let client = client.clone();
         async move {
-            match gh::check(client, &binary).await {
-                Ok(None) => Check::NotFound { binary },
-                Ok(Some(release)) => Check::Found { binary, release },
+            let cooldown = binary.options.cooldown.unwrap_or(config.cooldown());
+
+            match gh::check(client, &binary, cooldown).await {
+                Ok(Outcome {
+                    status: Status::Current | Status::Ahead(_),
+                    excluded,
+                }) => Check::NotFound { binary, excluded },
+                Ok(Outcome {
+                    status: Status::Outdated(release),
+                    excluded,
+                }) => Check::Found {
+                    binary,
+                    release,
+                    excluded,
+                },
                 Err(err) => Check::Error { binary, err },
             }
         }
@@ -227,12 +526,24 @@ async fn update(
         .with_label("checking")
         .await;
 
+    for check in &checks {
+        if let Check::NotFound { binary, excluded }
+        | Check::Found {
+            binary, excluded, ..
+        } = check
+        {
+            for excluded in excluded {
+                print_excluded(binary, excluded);
+            }
+        }
+    }
+
     let to_update = checks
         .iter()
         .filter_map(|check| match check {
-            Check::NotFound { binary: _ } => None,
-            Check::Found { binary, release: _ } => Some(binary.repo.to_string()),
-            Check::Error { binary: _, err: _ } => None,
+            Check::NotFound { .. } => None,
+            Check::Found { binary, .. } => Some(binary.repo.to_string()),
+            Check::Error { .. } => None,
         })
         .collect::<Vec<_>>();
 
@@ -248,25 +559,69 @@ async fn update(
 
     let mut group = strides::future::Group::new(progress_theme()).with_spinner_style(SPINNER_STYLE);
 
-    let mut others = Vec::new();
+    let keep = config.retained_versions();
+    let host = config.host();
+    let install_path = config.install_path().ok();
+
+    let mut others = held
+        .into_iter()
+        .map(|binary| Update::None { binary })
+        .collect::<Vec<_>>();
 
     for check in checks {
         match check {
-            Check::NotFound { binary } => {
+            Check::NotFound { binary, .. } => {
                 others.push(Update::None { binary });
             }
             Check::Found {
                 binary: old,
                 release,
+                ..
             } => {
+                let retained = match retain(&old, config) {
+                    Ok(retained) => retained,
+                    Err(err) => {
+                        others.push(Update::Error { binary: old, err });
+                        continue;
+                    }
+                };
+
                 let message = aligned_label("updating", &old.repo, update_width);
                 let (tx, rx) = unbounded_channel::<f64>();
+                let target = old.options.target.clone().unwrap_or_else(|| host.clone());
+                let in_install_path =
+                    old.paths.first().and_then(|path| path.parent()) == install_path.as_deref();
+                let data = (old.options.target.is_none() && in_install_path)
+                    .then(|| config.data_home())
+                    .flatten();
+                let foreign = installed
+                    .iter()
+                    .filter(|(repo, _)| *repo != old.repo)
+                    .map(|(_, path)| path.clone())
+                    .collect();
 
                 group.push(
                     async move {
-                        match gh::update(&old, release, tx).await {
-                            Ok(new) => Update::Installed { old, new },
-                            Err(err) => Update::Error { binary: old, err },
+                        match gh::update(&old, release, &target, data, foreign, tx).await {
+                            Ok(mut new) => {
+                                if let Some(retained) = retained {
+                                    new.push_retained(retained, keep);
+                                } else {
+                                    new.prune_retained(keep);
+                                }
+
+                                Update::Installed {
+                                    old_version: old.version,
+                                    new,
+                                }
+                            }
+                            Err(err) => {
+                                if let Some(retained) = retained {
+                                    retained.remove();
+                                }
+
+                                Update::Error { binary: old, err }
+                            }
                         }
                     }
                     .with_label(message)
@@ -292,12 +647,12 @@ async fn update(
         .into_iter()
         .map(|update| match update {
             Update::None { binary } => binary,
-            Update::Installed { old, new } => {
+            Update::Installed { old_version, new } => {
                 println!(
                     "{} {} ({} -> {})",
                     "Updated".bright_green(),
-                    old.repo,
-                    old.version,
+                    new.repo,
+                    old_version,
                     new.version
                 );
 
@@ -318,23 +673,99 @@ async fn update(
     Ok(Manifest { version, binaries })
 }
 
+/// Print the release notes of `releases` as plain text, indented below each tag.
+fn print_notes(releases: &[Release]) {
+    for release in releases {
+        println!("\n  {}", release.tag_name.bold());
+
+        let body = release
+            .body
+            .as_deref()
+            .map(markdown::to_text)
+            .unwrap_or_default();
+
+        if body.is_empty() {
+            println!("    {}", "no release notes".bright_black());
+        }
+
+        for line in body.lines() {
+            println!("    {line}");
+        }
+    }
+
+    println!();
+}
+
 /// Concurrently check all installed binaries listed in the manifest.
-async fn check(manifest: Manifest, token: Option<String>) -> Result<()> {
+async fn check(
+    config: &config::Config,
+    manifest: Manifest,
+    notes: bool,
+    explain: bool,
+    token: Option<String>,
+) -> Result<()> {
     enum Check {
         Update { binary: Binary, release: Release },
+        Held { binary: Binary, release: Release },
+        Ahead { binary: Binary, release: Release },
+        Excluded { binary: Binary, excluded: Excluded },
+        Notes { releases: Vec<Release> },
         Error { err: anyhow::Error },
     }
 
     let client = gh::make_client(token)?;
 
+    if explain {
+        let binaries = manifest.binaries.iter();
+        explain_assets(
+            &client,
+            binaries.map(|binary| (&binary.repo, &binary.options)),
+            config,
+        )
+        .await;
+    }
+
     let futs = manifest.binaries.into_iter().map(|binary| {
         let client = client.clone();
         async move {
-            match gh::check(client, &binary).await {
-                Ok(None) => None,
-                Ok(Some(release)) => Some(Check::Update { binary, release }),
-                Err(err) => Some(Check::Error { err }),
-            }
+            let cooldown = binary.options.cooldown.unwrap_or(config.cooldown());
+
+            let Outcome { status, excluded } =
+                match gh::check(client.clone(), &binary, cooldown).await {
+                    Ok(outcome) => outcome,
+                    Err(err) => return vec![Check::Error { err }],
+                };
+
+            let excluded = excluded
+                .into_iter()
+                .map(|excluded| Check::Excluded {
+                    binary: binary.clone(),
+                    excluded,
+                })
+                .collect::<Vec<_>>();
+
+            let notes = match &status {
+                Status::Outdated(release) if notes => {
+                    match gh::notes(client, &binary, &release.tag_name).await {
+                        Ok(releases) => Some(Check::Notes { releases }),
+                        Err(err) => Some(Check::Error { err }),
+                    }
+                }
+                _ => None,
+            };
+
+            let status = match status {
+                Status::Current => None,
+                Status::Outdated(release) if binary.held => Some(Check::Held { binary, release }),
+                Status::Outdated(release) => Some(Check::Update { binary, release }),
+                Status::Ahead(release) => Some(Check::Ahead { binary, release }),
+            };
+
+            status
+                .into_iter()
+                .chain(notes)
+                .chain(excluded)
+                .collect::<Vec<_>>()
         }
     });
 
@@ -358,6 +789,30 @@ async fn check(manifest: Manifest, token: Option<String>) -> Result<()> {
                     release.tag_name
                 );
             }
+            Check::Held { binary, release } => {
+                println!(
+                    "{} {} ({} -> {})",
+                    "Held".bright_yellow(),
+                    binary.repo,
+                    binary.version,
+                    release.tag_name
+                );
+            }
+            Check::Ahead { binary, release } => {
+                println!(
+                    "{} {} local {} is newer than latest {}",
+                    "Notice".bright_yellow(),
+                    binary.repo,
+                    binary.version,
+                    release.tag_name
+                );
+            }
+            Check::Excluded { binary, excluded } => {
+                print_excluded(&binary, &excluded);
+            }
+            Check::Notes { releases } => {
+                print_notes(&releases);
+            }
             Check::Error { err } => {
                 eprintln!("{err}");
             }
@@ -367,7 +822,7 @@ async fn check(manifest: Manifest, token: Option<String>) -> Result<()> {
     Ok(())
 }
 
-/// Rename `repo` found in the manifest's binaries.
+/// Rename the main executable of `repo` found in the manifest's binaries.
 fn rename(
     repo: Repo,
     Manifest {
@@ -379,21 +834,204 @@ fn rename(
         return Ok(Manifest { version, binaries });
     };
 
+    let taken = binaries
+        .iter()
+        .filter(|binary| binary.repo != repo)
+        .flat_map(Binary::installed)
+        .cloned()
+        .collect::<Vec<_>>();
+
     if let Some(index) = binaries.iter().position(|binary| binary.repo == repo)
         && let Some(elem) = binaries.get_mut(index)
+        && let Some(path) = elem.paths.first_mut()
     {
-        let from = elem.path.clone();
+        let from = path.clone();
+
+        path.pop();
+        path.push(new_name);
+
+        if taken.contains(path) {
+            return Err(anyhow!(
+                "refusing to overwrite {path:?} installed by another binary"
+            ));
+        }
+
+        std::fs::rename(&from, &path)?;
+        elem.repo.rename = Some(new_name.clone());
+
+        println!("{} {:?} -> {:?}", "Renamed".bright_green(), from, path);
+    }
+
+    Ok(Manifest { version, binaries })
+}
+
+/// Restore the most recently retained version of `repo` found in the manifest's binaries.
+async fn rollback(
+    repo: &Repo,
+    Manifest {
+        version,
+        mut binaries,
+    }: Manifest,
+) -> Result<Manifest> {
+    let binary = binaries
+        .iter_mut()
+        .find(|binary| binary.repo == *repo)
+        .ok_or_else(|| anyhow!("{repo} is not installed"))?;
+
+    if binary.retained.is_empty() {
+        return Err(anyhow!("no previous version of {repo} retained"));
+    }
+
+    let dir = binary
+        .paths
+        .first()
+        .and_then(|path| path.parent())
+        .ok_or_else(|| anyhow!("no installation directory of {repo} recorded"))?
+        .to_path_buf();
+
+    let previous = binary.retained.remove(0);
+    let mut restored = Vec::new();
+
+    for path in &previous.paths {
+        let name = path
+            .file_name()
+            .ok_or_else(|| anyhow!("{path:?} has no file name"))?;
+        let dest = dir.join(name);
+
+        // Renaming fails across file systems, so write a copy next to the destination that
+        // replaces it atomically instead.
+        if std::fs::rename(path, &dest).is_err() {
+            let mode = std::fs::metadata(path)?.permissions().mode();
+            let input = tokio::fs::File::open(path).await?;
+            extract::write_async(input, &dest, mode).await?;
+        }
+
+        restored.push(dest);
+    }
+
+    for stale in binary.paths.iter().filter(|path| !restored.contains(path)) {
+        if let Err(err) = std::fs::remove_file(stale) {
+            eprintln!("failed to remove {stale:?}: {err}");
+        }
+    }
+
+    previous.remove();
+    binary.paths = restored;
+
+    println!(
+        "{} {} ({} -> {})",
+        "Rolled back".bright_green(),
+        binary.repo,
+        binary.version,
+        previous.version
+    );
 
-        elem.path.pop();
-        elem.path.push(new_name);
-        std::fs::rename(&from, &elem.path)?;
+    binary.version = previous.version;
+
+    Ok(Manifest { version, binaries })
+}
 
-        println!("{} {:?} -> {:?}", "Renamed".bright_green(), from, elem.path);
+/// Set the held flag of all `repos` found in the manifest's binaries to `held`.
+fn pin(
+    repos: &[Repo],
+    held: bool,
+    Manifest {
+        version,
+        mut binaries,
+    }: Manifest,
+) -> Result<Manifest> {
+    for repo in repos {
+        let Some(binary) = binaries.iter_mut().find(|binary| binary.repo == *repo) else {
+            eprintln!("{}: {repo} is not installed", "Error".bright_red().bold());
+            continue;
+        };
+
+        binary.held = held;
+
+        let action = if held { "Pinned" } else { "Unpinned" };
+        println!(
+            "{} {} {}",
+            action.bright_green(),
+            binary.repo,
+            binary.version
+        );
     }
 
     Ok(Manifest { version, binaries })
 }
 
+/// Format `bytes` with a binary unit prefix.
+fn human_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut size = bytes as f64;
+    let mut unit = "B";
+
+    for next in UNITS {
+        if size < 1024.0 {
+            break;
+        }
+
+        size /= 1024.0;
+        unit = next;
+    }
+
+    format!("{size:.1} {unit}")
+}
+
+/// List recent releases of `repo` or, if a tag is given, the assets of that release as judged for
+/// `target`.
+async fn releases(
+    repo: Repo,
+    count: usize,
+    target: &host::Target,
+    token: Option<String>,
+) -> Result<()> {
+    let client = gh::make_client(token)?;
+
+    if repo.tag.is_none() {
+        for release in gh::releases(client, &repo, count).await? {
+            let date = release
+                .published_at
+                .map(|timestamp| timestamp.strftime("%Y-%m-%d").to_string())
+                .unwrap_or_default();
+
+            let flag = if release.draft {
+                "draft"
+            } else if release.prerelease {
+                "prerelease"
+            } else {
+                ""
+            };
+
+            println!(
+                "{:<24} {:<10} {}",
+                release.tag_name,
+                date.bright_black(),
+                flag.bright_yellow()
+            );
+        }
+
+        return Ok(());
+    }
+
+    let release = gh::release(client, &repo).await?;
+    let explanation = gh::explain_release(release, None, target)?;
+
+    println!("{repo} {} for {}", explanation.tag, explanation.target);
+
+    for verdict in &explanation.verdicts {
+        print_verdict(verdict, &format!(", {}", human_size(verdict.size)));
+    }
+
+    Ok(())
+}
+
 /// List all installed binaries in the `manifest`.
 fn list(manifest: &Manifest, format: Format) -> Result<()> {
     let mut binaries = manifest.binaries.iter().collect::<Vec<_>>();
@@ -403,25 +1041,21 @@ fn list(manifest: &Manifest, format: Format) -> Result<()> {
     match format {
         Format::Default => {
             for binary in binaries {
-                println!("{} {}", binary.repo, binary.version);
+                let held = if binary.held { " (pinned)" } else { "" };
+
+                println!(
+                    "{} {} {}{}",
+                    binary.repo,
+                    binary.version,
+                    binary.options.channel.bright_black(),
+                    held.bright_yellow(),
+                );
             }
         }
         Format::Install => {
             let output = binaries
                 .iter()
-                .map(|binary| {
-                    let Repo {
-                        owner,
-                        name,
-                        rename,
-                    } = &binary.repo;
-
-                    if let Some(rename) = rename {
-                        format!("{owner}/{name}:{rename}")
-                    } else {
-                        format!("{owner}/{name}")
-                    }
-                })
+                .map(|binary| binary.repo.spec())
                 .collect::<Vec<_>>()
                 .join(" ");
 
@@ -464,13 +1098,34 @@ async fn try_main() -> Result<()> {
                 &mut std::io::stdout(),
             );
         }
-        Commands::Install { repos } => install(repos, &config, manifest, token)
+        Commands::Install {
+            repos,
+            options,
+            install_path,
+            explain,
+        } => install(
+            repos,
+            &options,
+            install_path,
+            explain,
+            &config,
+            manifest,
+            token,
+        )
+        .await?
+        .save(&config)?,
+        Commands::Uninstall { repos } => uninstall(repos, manifest)?.save(&config)?,
+        Commands::Update { explain } => update(&config, manifest, explain, token)
             .await?
             .save(&config)?,
-        Commands::Uninstall { repos } => uninstall(repos, manifest)?.save(&config)?,
-        Commands::Update => update(manifest, token).await?.save(&config)?,
-        Commands::Check => check(manifest, token).await?,
+        Commands::Check { notes, explain } => {
+            check(&config, manifest, notes, explain, token).await?;
+        }
         Commands::Rename { repo } => rename(repo, manifest)?.save(&config)?,
+        Commands::Rollback { repo } => rollback(&repo, manifest).await?.save(&config)?,
+        Commands::Pin { repos } => pin(&repos, true, manifest)?.save(&config)?,
+        Commands::Unpin { repos } => pin(&repos, false, manifest)?.save(&config)?,
+        Commands::Releases { repo, count } => releases(repo, count, &config.host(), token).await?,
         Commands::List { format } => list(&manifest, format)?,
     }
 
@@ -1,12 +1,15 @@
 //! Manage the local installation manifest.
 
-use std::{cmp::Ordering, path::PathBuf};
+use std::cmp::Ordering;
+use std::path::{Path, PathBuf};
 
-use anyhow::{Result, anyhow};
+use anyhow::{Context, Result, anyhow};
 use owo_colors::OwoColorize;
+use semver::VersionReq;
 use serde::{Deserialize, Serialize};
 
 use crate::config::Config;
+use crate::host::Target;
 
 #[derive(Serialize, Deserialize, Debug, Default)]
 pub(crate) struct Manifest {
@@ -20,10 +23,96 @@ pub(crate) struct Manifest {
 pub(crate) struct Binary {
     /// Repository where this binary is from.
     pub repo: Repo,
-    /// Path to the binary executable.
-    pub path: PathBuf,
+    /// Paths to the installed executables, the first one being the main executable. Earlier
+    /// versions recorded a single `path`.
+    #[serde(alias = "path", deserialize_with = "one_or_many")]
+    pub paths: Vec<PathBuf>,
+    /// Paths to the installed shell completions and man pages.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub extras: Vec<PathBuf>,
     /// Installed version of the executable.
     pub version: String,
+    /// Options controlling which releases are followed.
+    #[serde(flatten)]
+    pub options: Options,
+    /// Whether updates are held back.
+    #[serde(default)]
+    pub held: bool,
+    /// Previously installed versions, most recent first.
+    #[serde(default)]
+    pub retained: Vec<Retained>,
+}
+
+/// Previously installed version kept for rollbacks.
+#[derive(Serialize, Deserialize, Debug, Clone, Eq, PartialEq)]
+pub(crate) struct Retained {
+    /// Version of the kept executables.
+    pub version: String,
+    /// Paths to the kept executables.
+    #[serde(alias = "path", deserialize_with = "one_or_many")]
+    pub paths: Vec<PathBuf>,
+}
+
+/// Deserialize a list of paths that earlier versions wrote as a single path.
+fn one_or_many<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> std::result::Result<Vec<PathBuf>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(PathBuf),
+        Many(Vec<PathBuf>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(path) => vec![path],
+        OneOrMany::Many(paths) => paths,
+    })
+}
+
+/// Per-binary options controlling which releases are followed.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Eq, PartialEq, clap::Args)]
+pub(crate) struct Options {
+    /// Release channel to follow.
+    #[arg(long, value_enum, default_value_t = Channel::Stable)]
+    #[serde(default)]
+    pub channel: Channel,
+    /// Only follow releases whose tag matches this regular expression.
+    #[arg(long, value_name = "REGEX", value_parser = parse_regex)]
+    pub tag_pattern: Option<String>,
+    /// Only install releases published at least this many days ago, overriding the configured
+    /// cooldown.
+    #[arg(long, value_name = "DAYS")]
+    pub cooldown: Option<u32>,
+    /// Only install assets whose name matches this regular expression.
+    #[arg(long, value_name = "REGEX", value_parser = parse_regex)]
+    pub asset: Option<String>,
+    /// Install assets for this `<arch>-<os>[-<libc>]` instead of the host, e.g.
+    /// `aarch64-linux-musl`.
+    #[arg(long, value_name = "TARGET")]
+    pub target: Option<Target>,
+    /// Only install executables matching this file name or, if it contains a `/`, path glob. The
+    /// first one given is the main executable.
+    #[arg(long = "bin", value_name = "GLOB")]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bin: Vec<String>,
+}
+
+/// Validate `s` as a regular expression.
+fn parse_regex(s: &str) -> Result<String> {
+    regex::Regex::new(s)?;
+    Ok(s.to_owned())
+}
+
+/// Kind of releases a binary follows.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, Eq, PartialEq, clap::ValueEnum)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum Channel {
+    /// Latest stable release.
+    #[default]
+    Stable,
+    /// Newest release including prereleases.
+    Prerelease,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Eq)]
@@ -34,6 +123,10 @@ pub(crate) struct Repo {
     pub name: String,
     /// Optional name of the binary
     pub rename: Option<String>,
+    /// Optional release tag to install instead of the latest release
+    pub tag: Option<String>,
+    /// Optional version constraint releases must satisfy
+    pub constraint: Option<VersionReq>,
 }
 
 impl PartialEq for Repo {
@@ -98,10 +191,26 @@ impl std::str::FromStr for Repo {
         let name = split.next().ok_or(anyhow!("{repo} is not a repo"))?;
         let rename = split.next().map(String::from);
 
+        let mut split = name.split('@');
+
+        let name = split.next().ok_or(anyhow!("{repo} is not a repo"))?;
+
+        let (tag, constraint) = match split.next() {
+            Some("") => return Err(anyhow!("{s} has an empty tag")),
+            Some(version) if version.starts_with(['^', '~', '<', '>', '=', '*']) => {
+                let constraint = VersionReq::parse(version)
+                    .with_context(|| format!("{version} is not a valid version constraint"))?;
+                (None, Some(constraint))
+            }
+            version => (version.map(String::from), None),
+        };
+
         Ok(Self {
             owner,
             name: name.to_owned(),
             rename,
+            tag,
+            constraint,
         })
     }
 }
@@ -117,6 +226,108 @@ impl std::fmt::Display for Repo {
     }
 }
 
+impl std::fmt::Display for Channel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Channel::Stable => write!(f, "stable"),
+            Channel::Prerelease => write!(f, "prerelease"),
+        }
+    }
+}
+
+impl Repo {
+    /// Uncolored `owner/name[@tag|@constraint][:rename]` representation that is parsed back by
+    /// [`std::str::FromStr`].
+    pub(crate) fn spec(&self) -> String {
+        let mut spec = format!("{}/{}", self.owner, self.name);
+
+        if let Some(tag) = &self.tag {
+            spec.push('@');
+            spec.push_str(tag);
+        }
+
+        if let Some(constraint) = &self.constraint {
+            spec.push('@');
+            spec.push_str(&constraint.to_string().replace(' ', ""));
+        }
+
+        if let Some(rename) = &self.rename {
+            spec.push(':');
+            spec.push_str(rename);
+        }
+
+        spec
+    }
+}
+
+impl Binary {
+    /// Copy the installed executables into `dir` so that they can be restored later.
+    pub(crate) fn retain(&self, dir: &Path) -> Result<Retained> {
+        let mut paths = Vec::new();
+
+        for path in &self.paths {
+            let name = path
+                .file_name()
+                .ok_or_else(|| anyhow!("{path:?} has no file name"))?;
+
+            let retained = dir.join(name);
+            std::fs::copy(path, &retained)?;
+            paths.push(retained);
+        }
+
+        Ok(Retained {
+            version: self.version.clone(),
+            paths,
+        })
+    }
+
+    /// Return the paths of all installed executables, shell completions and man pages.
+    pub(crate) fn installed(&self) -> impl Iterator<Item = &PathBuf> {
+        self.paths.iter().chain(&self.extras)
+    }
+
+    /// Remove all installed executables, shell completions and man pages, reporting but ignoring
+    /// failures.
+    pub(crate) fn remove(&self) {
+        for path in self.installed() {
+            if let Err(err) = std::fs::remove_file(path) {
+                eprintln!("failed to remove {path:?}: {err}");
+            }
+        }
+    }
+
+    /// Record `retained` as the most recent previous version and remove all but the `keep` most
+    /// recent ones from disk.
+    pub(crate) fn push_retained(&mut self, retained: Retained, keep: usize) {
+        self.retained.insert(0, retained);
+        self.prune_retained(keep);
+    }
+
+    /// Remove all but the `keep` most recent previous versions from disk.
+    pub(crate) fn prune_retained(&mut self, keep: usize) {
+        for old in self.retained.drain(keep.min(self.retained.len())..) {
+            old.remove();
+        }
+    }
+}
+
+impl Retained {
+    /// Remove the kept executables and their then empty version directory.
+    pub(crate) fn remove(&self) {
+        for path in &self.paths {
+            if let Err(err) = std::fs::remove_file(path)
+                && err.kind() != std::io::ErrorKind::NotFound
+            {
+                eprintln!("failed to remove {path:?}: {err}");
+            }
+        }
+
+        if let Some(parent) = self.paths.first().and_then(|path| path.parent()) {
+            let _ = std::fs::remove_dir(parent);
+        }
+    }
+}
+
 impl Manifest {
     pub(crate) fn load_or_create(config: &Config) -> Result<Self> {
         let path = config.manifest_path()?;
@@ -142,7 +353,9 @@ impl Manifest {
             .find(|existing| existing.repo == binary.repo)
         {
             existing.version = binary.version;
-            existing.path = binary.path;
+            existing.paths = binary.paths;
+            existing.extras = binary.extras;
+            existing.options = binary.options;
         } else {
             self.binaries.push(binary);
         }
@@ -186,4 +399,151 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn parse_repo_with_tag() -> Result<()> {
+        assert!(Repo::from_str("foo/bar@").is_err());
+
+        let repo = Repo::from_str("foo/bar@v1.2.3")?;
+        assert_eq!(repo.name, "bar");
+        assert_eq!(repo.tag.unwrap(), "v1.2.3");
+        assert!(repo.rename.is_none());
+
+        let repo = Repo::from_str("foo/bar@v1.2.3:baz")?;
+        assert_eq!(repo.name, "bar");
+        assert_eq!(repo.tag.unwrap(), "v1.2.3");
+        assert_eq!(repo.rename.unwrap(), "baz");
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_repo_with_constraint() -> Result<()> {
+        assert!(Repo::from_str("foo/bar@^x").is_err());
+
+        let repo = Repo::from_str("foo/bar@^1.4:baz")?;
+        assert!(repo.tag.is_none());
+        assert_eq!(repo.rename.unwrap(), "baz");
+
+        let constraint = repo.constraint.unwrap();
+        assert!(constraint.matches(&semver::Version::new(1, 9, 0)));
+        assert!(!constraint.matches(&semver::Version::new(2, 0, 0)));
+
+        let repo = Repo::from_str("foo/bar@>=1.2,<2")?;
+        assert!(repo.constraint.is_some());
+
+        Ok(())
+    }
+
+    #[test]
+    fn manifest_roundtrip() -> Result<()> {
+        let serialized = r#"
+            version = 0
+
+            [[binaries]]
+            path = "/home/user/.local/bin/fd"
+            version = "v10.2.0"
+
+            [binaries.repo]
+            owner = "sharkdp"
+            name = "fd"
+
+            [[binaries.retained]]
+            version = "v10.1.0"
+            path = "/home/user/.local/share/binge/versions/sharkdp/fd/v10.1.0/fd"
+        "#;
+
+        let manifest: Manifest = toml::from_str(serialized)?;
+        let binary = &manifest.binaries[0];
+        assert_eq!(binary.paths, [PathBuf::from("/home/user/.local/bin/fd")]);
+        assert_eq!(binary.retained[0].paths.len(), 1);
+        assert_eq!(binary.options, Options::default());
+        assert!(binary.extras.is_empty());
+        assert!(!binary.held);
+
+        let mut manifest = manifest;
+        manifest.binaries[0].options = Options {
+            channel: Channel::Prerelease,
+            tag_pattern: Some("^cli-".into()),
+            cooldown: Some(7),
+            asset: Some("-full".into()),
+            target: Some("aarch64-linux-musl".parse()?),
+            bin: vec!["uv".into(), "bin/uvx".into()],
+        };
+        manifest.binaries[0].repo.constraint = Some(VersionReq::parse("^10")?);
+        manifest.binaries[0]
+            .paths
+            .push("/home/user/.local/bin/fdx".into());
+        manifest.binaries[0]
+            .extras
+            .push("/home/user/.local/share/man/man1/fd.1".into());
+
+        let deserialized: Manifest = toml::from_str(&toml::to_string_pretty(&manifest)?)?;
+        let binary = &deserialized.binaries[0];
+        assert_eq!(binary, &manifest.binaries[0]);
+        assert_eq!(binary.options.tag_pattern.as_deref(), Some("^cli-"));
+        assert_eq!(binary.options.cooldown, Some(7));
+        assert_eq!(
+            binary.options.target.as_ref().map(ToString::to_string),
+            Some("aarch64-linux-musl".into())
+        );
+        assert_eq!(binary.repo.constraint, Some(VersionReq::parse("^10")?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn push_retained() -> Result<()> {
+        let mut binary = Binary {
+            repo: Repo::from_str("foo/bar")?,
+            paths: vec![PathBuf::from("/nonexistent/bar")],
+            extras: Vec::new(),
+            version: "v3".into(),
+            options: Options::default(),
+            held: false,
+            retained: Vec::new(),
+        };
+
+        for version in ["v0", "v1", "v2"] {
+            let retained = Retained {
+                version: version.into(),
+                paths: vec![PathBuf::from("/nonexistent").join(version).join("bar")],
+            };
+
+            binary.push_retained(retained, 2);
+        }
+
+        let versions = binary
+            .retained
+            .iter()
+            .map(|retained| retained.version.as_str())
+            .collect::<Vec<_>>();
+
+        assert_eq!(versions, ["v2", "v1"]);
+
+        binary.prune_retained(1);
+        assert_eq!(binary.retained.len(), 1);
+        assert_eq!(binary.retained[0].version, "v2");
+
+        binary.push_retained(binary.retained[0].clone(), 0);
+        assert!(binary.retained.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn repo_spec_roundtrip() -> Result<()> {
+        for spec in [
+            "foo/bar",
+            "foo/bar:baz",
+            "foo/bar@v1.2.3",
+            "foo/bar@v1.2.3:baz",
+            "foo/bar@^1.4:baz",
+            "foo/bar@>=1.2,<2",
+        ] {
+            assert_eq!(Repo::from_str(spec)?.spec(), spec);
+        }
+
+        Ok(())
+    }
 }
@@ -0,0 +1,131 @@
+//! Render release notes written in Markdown as plain terminal text.
+
+use regex::Regex;
+
+/// Strip Markdown syntax from `markdown` and return readable plain text.
+pub(crate) fn to_text(markdown: &str) -> String {
+    let comment = Regex::new(r"(?s)<!--.*?-->").expect("compiling the regex");
+    let image = Regex::new(r"!\[([^\]]*)\]\([^)]*\)").expect("compiling the regex");
+    let hyperlink = Regex::new(r"\[([^\]]+)\]\([^)]*\)").expect("compiling the regex");
+    let autolink = Regex::new(r"<(https?://[^>]+)>").expect("compiling the regex");
+    let html = Regex::new(r"</?[A-Za-z][^>]*>").expect("compiling the regex");
+    let strong = Regex::new(r"(\*\*|__)([^*_]+?)(\*\*|__)").expect("compiling the regex");
+    let emphasis = Regex::new(r"\*([^*\s][^*]*?)\*").expect("compiling the regex");
+    let code = Regex::new(r"`([^`]+)`").expect("compiling the regex");
+    let placeholder = Regex::new(r"\x00(\d+)\x00").expect("compiling the regex");
+    let heading = Regex::new(r"^#{1,6}\s+").expect("compiling the regex");
+    let bullet = Regex::new(r"^(\s*)[-*+]\s+").expect("compiling the regex");
+    let rule = Regex::new(r"^\s*([-*_]\s*){3,}$").expect("compiling the regex");
+    let table_separator =
+        Regex::new(r"^\s*\|?(\s*:?-+:?\s*\|)+\s*:?-*:?\s*$").expect("compiling the regex");
+
+    let markdown = markdown.replace("\r\n", "\n");
+    let markdown = comment.replace_all(&markdown, "");
+
+    let mut lines = Vec::new();
+    let mut in_code_block = false;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") || line.trim_start().starts_with("~~~") {
+            in_code_block = !in_code_block;
+            continue;
+        }
+
+        if in_code_block {
+            lines.push(format!("  {line}"));
+            continue;
+        }
+
+        if rule.is_match(line) || table_separator.is_match(line) {
+            continue;
+        }
+
+        let line = line.trim_end();
+        let line = line.trim_start_matches("> ").trim_start_matches('>');
+        let line = heading.replace(line, "");
+        let line = bullet.replace(&line, "$1• ");
+
+        // Set code spans aside so that their contents are not mistaken for markup.
+        let mut spans = Vec::new();
+        let line = code.replace_all(&line, |caps: &regex::Captures| {
+            spans.push(caps[1].to_string());
+            format!("\x00{}\x00", spans.len() - 1)
+        });
+
+        let line = image.replace_all(&line, "$1");
+        let line = hyperlink.replace_all(&line, "$1");
+        let line = autolink.replace_all(&line, "$1");
+        let line = html.replace_all(&line, "");
+        let line = strong.replace_all(&line, "$2");
+        let line = emphasis.replace_all(&line, "$1");
+        let line = placeholder.replace_all(&line, |caps: &regex::Captures| {
+            caps[1]
+                .parse::<usize>()
+                .ok()
+                .and_then(|index| spans.get(index))
+                .cloned()
+                .unwrap_or_default()
+        });
+
+        let blank = line.trim().is_empty();
+
+        if blank && lines.last().is_none_or(|last: &String| last.is_empty()) {
+            continue;
+        }
+
+        lines.push(if blank {
+            String::new()
+        } else {
+            line.into_owned()
+        });
+    }
+
+    while lines.last().is_some_and(String::is_empty) {
+        lines.pop();
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strip_inline() {
+        assert_eq!(
+            to_text("Fix **bold** and *emphasis* in `code`"),
+            "Fix bold and emphasis in code"
+        );
+        assert_eq!(
+            to_text("See [the docs](https://example.com) and <https://foo.com>"),
+            "See the docs and https://foo.com"
+        );
+        assert_eq!(
+            to_text("![logo](logo.png) <b>snake_case</b>"),
+            "logo snake_case"
+        );
+        assert_eq!(
+            to_text("Accept `Vec<u8>` in `__init__` and [`foo`](https://foo.com)"),
+            "Accept Vec<u8> in __init__ and foo"
+        );
+    }
+
+    #[test]
+    fn strip_blocks() {
+        let markdown = "## Features\r\n\r\n\r\n* one\n  - two\n\n---\n\n```sh\ncargo install\n```\n\n> quoted\n<!-- hidden -->\n";
+
+        assert_eq!(
+            to_text(markdown),
+            "Features\n\n• one\n  • two\n\n  cargo install\n\nquoted"
+        );
+    }
+
+    #[test]
+    fn strip_tables() {
+        assert_eq!(
+            to_text("| a | b |\n|---|:-:|\n| 1 | 2 |"),
+            "| a | b |\n| 1 | 2 |"
+        );
+    }
+}
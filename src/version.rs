@@ -0,0 +1,105 @@
+//! Lenient interpretation of release tags as versions.
+
+use std::cmp::Ordering;
+
+use regex::Regex;
+use semver::{BuildMetadata, Prerelease, Version};
+
+/// Parse a release `tag` into a [`Version`].
+///
+/// Any non-numeric prefix such as `v` or `release-` is skipped, missing minor and patch components
+/// default to zero and date-style tags like `2024-05-01` map to `2024.5.1`. Returns `None` if the
+/// tag does not look like a version at all.
+pub(crate) fn parse(tag: &str) -> Option<Version> {
+    let tag = tag.trim_start_matches(|c: char| !c.is_ascii_digit());
+
+    let date = Regex::new(r"^(\d{4})-(\d{2})-(\d{2})(?:[-_.+]([0-9A-Za-z.-]+))?$")
+        .expect("compiling the regex");
+
+    if let Some(captures) = date.captures(tag) {
+        let mut version = Version::new(
+            captures[1].parse().ok()?,
+            captures[2].parse().ok()?,
+            captures[3].parse().ok()?,
+        );
+
+        if let Some(suffix) = captures.get(4) {
+            version.build = BuildMetadata::new(suffix.as_str()).ok()?;
+        }
+
+        return Some(version);
+    }
+
+    let expr = Regex::new(
+        r"^(\d+)(?:\.(\d+))?(?:\.(\d+))?(?:-?([0-9A-Za-z][0-9A-Za-z.-]*))?(?:\+([0-9A-Za-z.-]+))?$",
+    )
+    .expect("compiling the regex");
+
+    let captures = expr.captures(tag)?;
+    let component = |index: usize| -> Option<u64> {
+        captures
+            .get(index)
+            .map_or(Some(0), |m| m.as_str().parse().ok())
+    };
+
+    let mut version = Version::new(component(1)?, component(2)?, component(3)?);
+
+    if let Some(pre) = captures.get(4) {
+        version.pre = Prerelease::new(pre.as_str()).ok()?;
+    }
+
+    if let Some(build) = captures.get(5) {
+        version.build = BuildMetadata::new(build.as_str()).ok()?;
+    }
+
+    Some(version)
+}
+
+/// Compare the `installed` tag with the `available` one. Returns `None` if either of them cannot be
+/// parsed as a version.
+pub(crate) fn compare(installed: &str, available: &str) -> Option<Ordering> {
+    Some(parse(installed)?.cmp(&parse(available)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_prefixed() {
+        assert_eq!(parse("1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(parse("v1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(parse("release-1.2.3"), Some(Version::new(1, 2, 3)));
+        assert_eq!(parse("v1.2"), Some(Version::new(1, 2, 0)));
+        assert_eq!(parse("v14"), Some(Version::new(14, 0, 0)));
+        assert_eq!(parse("1.2.3.4"), None);
+        assert_eq!(parse("nightly"), None);
+        assert_eq!(parse(""), None);
+    }
+
+    #[test]
+    fn parse_prerelease() {
+        let version = parse("v1.2.3-rc.1").unwrap();
+        assert_eq!(version.pre.as_str(), "rc.1");
+
+        let version = parse("v0.18.0-beta").unwrap();
+        assert_eq!(version.pre.as_str(), "beta");
+    }
+
+    #[test]
+    fn parse_date() {
+        assert_eq!(parse("2024-05-01"), Some(Version::new(2024, 5, 1)));
+        assert_eq!(parse("release-2024.05.01"), Some(Version::new(2024, 5, 1)));
+        assert_eq!(parse("20240501"), Some(Version::new(20_240_501, 0, 0)));
+    }
+
+    #[test]
+    fn compare_tags() {
+        assert_eq!(compare("v1.2.3", "1.2.3"), Some(Ordering::Equal));
+        assert_eq!(compare("v1.2.3", "v1.10.0"), Some(Ordering::Less));
+        assert_eq!(compare("v2.0.0", "v1.10.0"), Some(Ordering::Greater));
+        assert_eq!(compare("v1.0.0-rc.1", "v1.0.0"), Some(Ordering::Less));
+        assert_eq!(compare("2024-05-01", "2024-12-24"), Some(Ordering::Less));
+        assert_eq!(compare("nightly", "v1.0.0"), None);
+    }
+}